@@ -0,0 +1,65 @@
+#[macro_use]
+extern crate criterion;
+use criterion::{black_box, Criterion};
+
+extern crate gtin_validate;
+use gtin_validate::{gtin13, CachedChecker};
+
+// Build a Zipf-distributed workload: the code of rank `r` (counting from
+// 1) appears with probability proportional to 1/r, so a handful of codes
+// dominate, as in the skewed workloads the cache is meant for.
+fn zipfian_workload(distinct: usize, len: usize) -> Vec<String> {
+    let codes: Vec<String> = (0..distinct)
+        .map(|i| {
+            let body = format!("{:012}", 400_638_133_393u64 + i as u64 * 7919);
+            let check = gtin13::check_digit_for_prefix(&body).unwrap();
+            format!("{}{}", body, check)
+        })
+        .collect();
+
+    let weights: Vec<f64> = (1..=distinct).map(|rank| 1.0 / rank as f64).collect();
+    let total: f64 = weights.iter().sum();
+
+    // A small xorshift generator keeps the workload deterministic.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let mut target = (state >> 11) as f64 / (1u64 << 53) as f64 * total;
+            let mut rank = 0;
+            while rank + 1 < distinct && target >= weights[rank] {
+                target -= weights[rank];
+                rank += 1;
+            }
+            codes[rank].clone()
+        })
+        .collect()
+}
+
+fn bench_cache(c: &mut Criterion) {
+    let workload = zipfian_workload(10_000, 10_000);
+
+    c.bench_function("gtin13 check - zipfian uncached", |b| {
+        b.iter(|| {
+            workload
+                .iter()
+                .filter(|code| gtin13::check(black_box(code.as_str())))
+                .count()
+        })
+    });
+
+    let checker = CachedChecker::new(1_000);
+    c.bench_function("gtin13 check - zipfian cached", |b| {
+        b.iter(|| {
+            workload
+                .iter()
+                .filter(|code| checker.check13(black_box(code.as_str())))
+                .count()
+        })
+    });
+}
+
+criterion_group!(cache, bench_cache);
+criterion_main!(cache);
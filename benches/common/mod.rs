@@ -0,0 +1,44 @@
+// Input shared by the per-width benches.
+
+/// Build a deterministic column of `len` inputs for a GTIN of `width`
+/// digits, mixing the outcomes seen in real data: in every 20 inputs,
+/// 14 are valid, 3 have a bad check digit, 2 contain a non-digit
+/// character and 1 has lost its leading digit.
+pub fn mixed_dataset(width: usize, len: usize) -> Vec<String> {
+    // A small xorshift generator keeps the dataset deterministic.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..len)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            let body: String = format!("{:020}", state)[20 - (width - 1)..].to_string();
+            let mut code = format!("{}{}", body, check_digit(&body));
+            match i % 20 {
+                14..=16 => {
+                    let last = code.pop().unwrap();
+                    let wrong = (last as u8 - b'0' + 1) % 10;
+                    code.push(char::from(b'0' + wrong));
+                }
+                17 | 18 => code.replace_range(width / 2..width / 2 + 1, "O"),
+                19 => {
+                    code.remove(0);
+                }
+                _ => {}
+            }
+            code
+        })
+        .collect()
+}
+
+// The GS1 mod-10 check digit, computed independently of the crate.
+fn check_digit(body: &str) -> u32 {
+    let sum: u32 = body
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| u32::from(b - b'0') * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    (10 - sum % 10) % 10
+}
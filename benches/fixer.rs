@@ -0,0 +1,85 @@
+#[macro_use]
+extern crate criterion;
+use criterion::{black_box, Criterion};
+
+extern crate gtin_validate;
+use gtin_validate::gtin13;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+
+// Count every allocation so the bench can show that a warmed-up `Fixer`
+// never allocates.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn bench_fixer(c: &mut Criterion) {
+    let column = common::mixed_dataset(13, 1000);
+    let mut fixer = gtin13::Fixer::new();
+
+    // Warm up, then fix the whole column many times over, counting
+    // allocations along the way.
+    for code in &column {
+        let _ = fixer.fix(code);
+    }
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let mut fixed = 0;
+    for _ in 0..100 {
+        for code in &column {
+            if fixer.fix(black_box(code)).is_ok() {
+                fixed += 1;
+            }
+        }
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "gtin13 Fixer: {} allocations across {} calls ({} fixed)",
+        allocations,
+        column.len() * 100,
+        fixed
+    );
+    assert_eq!(allocations, 0);
+
+    c.bench_function("gtin13 Fixer::fix - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| fixer.fix(black_box(code.as_str())).is_ok())
+                .count()
+        })
+    });
+
+    c.bench_function("gtin13 fix - mixed column (allocating)", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin13::fix(black_box(code.as_str())).is_ok())
+                .count()
+        })
+    });
+}
+
+criterion_group!(fixer, bench_fixer);
+criterion_main!(fixer);
@@ -1,23 +1,102 @@
 #[macro_use]
 extern crate criterion;
-use criterion::Criterion;
+use criterion::{black_box, Criterion};
 
 extern crate gtin_validate;
 use gtin_validate::gtin12;
 
+mod common;
+
 fn bench_check(c: &mut Criterion) {
-    c.bench_function_over_inputs(
-        "gtin12 check",
-        |b, &code| b.iter(|| gtin12::check(code)),
-        &["000000000000"],
-    );
-
-    c.bench_function_over_inputs(
-        "gtin12 check - too long",
-        |b, &code| b.iter(|| gtin12::check(code)),
-        &["01234567890123456789012345678901234567890123456789"],
-    );
+    c.bench_function("gtin12 check", |b| {
+        b.iter(|| gtin12::check(black_box("000000000000")))
+    });
+
+    c.bench_function("gtin12 check - too long", |b| {
+        b.iter(|| {
+            gtin12::check(black_box(
+                "01234567890123456789012345678901234567890123456789",
+            ))
+        })
+    });
+
+    c.bench_function("gtin12 check - non-numeric", |b| {
+        b.iter(|| gtin12::check(black_box("SKU-44710-XL")))
+    });
+}
+
+fn bench_sparse_column(c: &mut Criterion) {
+    // Nine empty fields for every populated one.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| if i % 10 == 0 { "036000291452" } else { "" })
+        .collect();
+
+    c.bench_function("gtin12 check - sparse column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin12::check(black_box(**code)))
+                .count()
+        })
+    });
+}
+
+fn bench_mixed_column(c: &mut Criterion) {
+    let column = common::mixed_dataset(12, 1000);
+
+    c.bench_function("gtin12 check - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin12::check(black_box(code.as_str())))
+                .count()
+        })
+    });
+
+    c.bench_function("gtin12 fix - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin12::fix(black_box(code.as_str())).is_ok())
+                .count()
+        })
+    });
+}
+
+fn bench_fix(c: &mut Criterion) {
+    c.bench_function("gtin12 fix", |b| {
+        b.iter(|| gtin12::fix(black_box("036000291452")))
+    });
+
+    c.bench_function("gtin12 fix - padded", |b| {
+        b.iter(|| gtin12::fix(black_box("36000291452")))
+    });
+
+    // Mostly clean data: one code in ten needs padding.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| {
+            if i % 10 == 0 {
+                "36000291452"
+            } else {
+                "036000291452"
+            }
+        })
+        .collect();
+    c.bench_function("gtin12 fix - mostly clean column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin12::fix(black_box(**code)).is_ok())
+                .count()
+        })
+    });
 }
 
-criterion_group!(gtin12, bench_check);
+criterion_group!(
+    gtin12,
+    bench_check,
+    bench_sparse_column,
+    bench_mixed_column,
+    bench_fix
+);
 criterion_main!(gtin12);
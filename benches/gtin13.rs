@@ -1,23 +1,137 @@
 #[macro_use]
 extern crate criterion;
-use criterion::Criterion;
+use criterion::{black_box, Criterion};
 
 extern crate gtin_validate;
 use gtin_validate::gtin13;
 
+mod common;
+
 fn bench_check(c: &mut Criterion) {
-    c.bench_function_over_inputs(
-        "gtin13 check",
-        |b, &code| b.iter(|| gtin13::check(code)),
-        &["0000000000000"],
-    );
-
-    c.bench_function_over_inputs(
-        "gtin13 check - too long",
-        |b, &code| b.iter(|| gtin13::check(code)),
-        &["01234567890123456789012345678901234567890123456789"],
-    );
+    c.bench_function("gtin13 check", |b| {
+        b.iter(|| gtin13::check(black_box("0000000000000")))
+    });
+
+    c.bench_function("gtin13 check - too long", |b| {
+        b.iter(|| {
+            gtin13::check(black_box(
+                "01234567890123456789012345678901234567890123456789",
+            ))
+        })
+    });
+
+    c.bench_function("gtin13 check - non-numeric", |b| {
+        b.iter(|| gtin13::check(black_box("SKU-447100-XL")))
+    });
+}
+
+// The two-pass algorithm `gtin13::check` used before it was fused into
+// a single pass: validate every byte, then compute the check digit.
+fn check_two_pass(code: &str) -> bool {
+    if code.len() != 13 || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let bytes = code.as_bytes();
+    let sum: u32 = bytes[..12]
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &b)| u32::from(b - b'0') * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    (10 - sum % 10) % 10 == u32::from(bytes[12] - b'0')
+}
+
+fn bench_single_pass(c: &mut Criterion) {
+    let inputs = [
+        ("valid", "4006381333931"),
+        ("bad check digit", "4006381333932"),
+        ("non-numeric", "SKU-447100-XL"),
+        ("late non-digit", "400638133393X"),
+    ];
+    for &(name, code) in &inputs {
+        assert_eq!(gtin13::check(code), check_two_pass(code));
+        c.bench_function(&format!("gtin13 check one-pass - {}", name), |b| {
+            b.iter(|| gtin13::check(black_box(code)))
+        });
+        c.bench_function(&format!("gtin13 check two-pass - {}", name), |b| {
+            b.iter(|| check_two_pass(black_box(code)))
+        });
+    }
+}
+
+fn bench_sparse_column(c: &mut Criterion) {
+    // Nine empty fields for every populated one.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| if i % 10 == 0 { "4006381333931" } else { "" })
+        .collect();
+
+    c.bench_function("gtin13 check - sparse column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin13::check(black_box(**code)))
+                .count()
+        })
+    });
+}
+
+fn bench_mixed_column(c: &mut Criterion) {
+    let column = common::mixed_dataset(13, 1000);
+
+    c.bench_function("gtin13 check - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin13::check(black_box(code.as_str())))
+                .count()
+        })
+    });
+
+    c.bench_function("gtin13 fix - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin13::fix(black_box(code.as_str())).is_ok())
+                .count()
+        })
+    });
+}
+
+fn bench_fix(c: &mut Criterion) {
+    c.bench_function("gtin13 fix", |b| {
+        b.iter(|| gtin13::fix(black_box("4006381333931")))
+    });
+
+    c.bench_function("gtin13 fix - padded", |b| {
+        b.iter(|| gtin13::fix(black_box("495205944325")))
+    });
+
+    // Mostly clean data: one code in ten needs padding.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| {
+            if i % 10 == 0 {
+                "495205944325"
+            } else {
+                "4006381333931"
+            }
+        })
+        .collect();
+    c.bench_function("gtin13 fix - mostly clean column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin13::fix(black_box(**code)).is_ok())
+                .count()
+        })
+    });
 }
 
-criterion_group!(gtin13, bench_check);
+criterion_group!(
+    gtin13,
+    bench_check,
+    bench_single_pass,
+    bench_sparse_column,
+    bench_mixed_column,
+    bench_fix
+);
 criterion_main!(gtin13);
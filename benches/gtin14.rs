@@ -1,23 +1,102 @@
 #[macro_use]
 extern crate criterion;
-use criterion::Criterion;
+use criterion::{black_box, Criterion};
 
 extern crate gtin_validate;
 use gtin_validate::gtin14;
 
+mod common;
+
 fn bench_check(c: &mut Criterion) {
-    c.bench_function_over_inputs(
-        "gtin14 check",
-        |b, &code| b.iter(|| gtin14::check(code)),
-        &["00000000000000"],
-    );
-
-    c.bench_function_over_inputs(
-        "gtin14 check - too long",
-        |b, &code| b.iter(|| gtin14::check(code)),
-        &["01234567890123456789012345678901234567890123456789"],
-    );
+    c.bench_function("gtin14 check", |b| {
+        b.iter(|| gtin14::check(black_box("00000000000000")))
+    });
+
+    c.bench_function("gtin14 check - too long", |b| {
+        b.iter(|| {
+            gtin14::check(black_box(
+                "01234567890123456789012345678901234567890123456789",
+            ))
+        })
+    });
+
+    c.bench_function("gtin14 check - non-numeric", |b| {
+        b.iter(|| gtin14::check(black_box("SKU-4471000-XL")))
+    });
+}
+
+fn bench_sparse_column(c: &mut Criterion) {
+    // Nine empty fields for every populated one.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| if i % 10 == 0 { "14567815983469" } else { "" })
+        .collect();
+
+    c.bench_function("gtin14 check - sparse column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin14::check(black_box(**code)))
+                .count()
+        })
+    });
+}
+
+fn bench_mixed_column(c: &mut Criterion) {
+    let column = common::mixed_dataset(14, 1000);
+
+    c.bench_function("gtin14 check - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin14::check(black_box(code.as_str())))
+                .count()
+        })
+    });
+
+    c.bench_function("gtin14 fix - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin14::fix(black_box(code.as_str())).is_ok())
+                .count()
+        })
+    });
+}
+
+fn bench_fix(c: &mut Criterion) {
+    c.bench_function("gtin14 fix", |b| {
+        b.iter(|| gtin14::fix(black_box("14567815983469")))
+    });
+
+    c.bench_function("gtin14 fix - padded", |b| {
+        b.iter(|| gtin14::fix(black_box("4006381333931")))
+    });
+
+    // Mostly clean data: one code in ten needs padding.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| {
+            if i % 10 == 0 {
+                "4006381333931"
+            } else {
+                "14567815983469"
+            }
+        })
+        .collect();
+    c.bench_function("gtin14 fix - mostly clean column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin14::fix(black_box(**code)).is_ok())
+                .count()
+        })
+    });
 }
 
-criterion_group!(gtin14, bench_check);
+criterion_group!(
+    gtin14,
+    bench_check,
+    bench_sparse_column,
+    bench_mixed_column,
+    bench_fix
+);
 criterion_main!(gtin14);
@@ -1,23 +1,96 @@
 #[macro_use]
 extern crate criterion;
-use criterion::Criterion;
+use criterion::{black_box, Criterion};
 
 extern crate gtin_validate;
 use gtin_validate::gtin8;
 
+mod common;
+
 fn bench_check(c: &mut Criterion) {
-    c.bench_function_over_inputs(
-        "gtin8 check",
-        |b, &code| b.iter(|| gtin8::check(code)),
-        &["00000000"],
-    );
-
-    c.bench_function_over_inputs(
-        "gtin8 check - too long",
-        |b, &code| b.iter(|| gtin8::check(code)),
-        &["01234567890123456789012345678901234567890123456789"],
-    );
+    c.bench_function("gtin8 check", |b| {
+        b.iter(|| gtin8::check(black_box("00000000")))
+    });
+
+    c.bench_function("gtin8 check - too long", |b| {
+        b.iter(|| {
+            gtin8::check(black_box(
+                "01234567890123456789012345678901234567890123456789",
+            ))
+        })
+    });
+
+    c.bench_function("gtin8 check - non-numeric", |b| {
+        b.iter(|| gtin8::check(black_box("SKU-4471")))
+    });
+}
+
+fn bench_sparse_column(c: &mut Criterion) {
+    // Nine empty fields for every populated one.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| if i % 10 == 0 { "14567810" } else { "" })
+        .collect();
+
+    c.bench_function("gtin8 check - sparse column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin8::check(black_box(**code)))
+                .count()
+        })
+    });
+}
+
+fn bench_mixed_column(c: &mut Criterion) {
+    let column = common::mixed_dataset(8, 1000);
+
+    c.bench_function("gtin8 check - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin8::check(black_box(code.as_str())))
+                .count()
+        })
+    });
+
+    c.bench_function("gtin8 fix - mixed column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin8::fix(black_box(code.as_str())).is_ok())
+                .count()
+        })
+    });
+}
+
+fn bench_fix(c: &mut Criterion) {
+    c.bench_function("gtin8 fix", |b| {
+        b.iter(|| gtin8::fix(black_box("14567810")))
+    });
+
+    c.bench_function("gtin8 fix - padded", |b| {
+        b.iter(|| gtin8::fix(black_box("4567810")))
+    });
+
+    // Mostly clean data: one code in ten needs padding.
+    let column: Vec<&str> = (0..1000)
+        .map(|i| if i % 10 == 0 { "4567810" } else { "14567810" })
+        .collect();
+    c.bench_function("gtin8 fix - mostly clean column", |b| {
+        b.iter(|| {
+            column
+                .iter()
+                .filter(|code| gtin8::fix(black_box(**code)).is_ok())
+                .count()
+        })
+    });
 }
 
-criterion_group!(gtin8, bench_check);
+criterion_group!(
+    gtin8,
+    bench_check,
+    bench_sparse_column,
+    bench_mixed_column,
+    bench_fix
+);
 criterion_main!(gtin8);
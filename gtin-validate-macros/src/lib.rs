@@ -0,0 +1,157 @@
+//! Compile-time validated GTIN literals for the `gtin-validate` crate.
+//!
+//! The [`gtin13!`](macro.gtin13.html) macro checks a string literal while
+//! the crate is being compiled, so an invalid code embedded in source is
+//! reported as a build error pointing at the literal rather than being
+//! discovered at run time.
+
+#![forbid(unsafe_code)]
+
+extern crate gtin_validate;
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use gtin_validate::gtin13;
+
+/// Validate a GTIN-13 string literal at compile time and expand to the
+/// corresponding [`Gtin13`](../gtin_validate/gtin13/struct.Gtin13.html).
+///
+/// The literal must be exactly 13 ASCII digits with a correct check
+/// digit, as for `gtin13::check`; it is not trimmed or padded. Anything
+/// else fails the build with an error describing the problem.
+///
+/// # Examples
+/// ```
+/// extern crate gtin_validate;
+/// extern crate gtin_validate_macros;
+///
+/// use gtin_validate::Gtin13;
+/// use gtin_validate_macros::gtin13;
+///
+/// # fn main() {
+/// let code: Gtin13 = gtin13!("4006381333931");
+/// assert_eq!(code.as_str(), "4006381333931");
+/// # }
+/// ```
+///
+/// An invalid code does not compile:
+///
+/// ```compile_fail
+/// extern crate gtin_validate_macros;
+///
+/// use gtin_validate_macros::gtin13;
+///
+/// # fn main() {
+/// let code = gtin13!("4006381333932"); // Bad check digit
+/// # }
+/// ```
+#[proc_macro]
+pub fn gtin13(input: TokenStream) -> TokenStream {
+    let (code, span) = match string_literal(input) {
+        Ok(literal) => literal,
+        Err((span, message)) => return compile_error(span, &message),
+    };
+    if let Err(message) = validate13(&code) {
+        return compile_error(span, &format!("invalid GTIN-13 literal: {}", message));
+    }
+
+    format!(
+        "<::gtin_validate::gtin13::Gtin13 as ::std::convert::TryFrom<&str>>::try_from({:?})\
+         .expect(\"validated at compile time\")",
+        code
+    )
+    .parse()
+    .unwrap()
+}
+
+// Describe why a code is not a valid GTIN-13.
+fn validate13(code: &str) -> Result<(), String> {
+    if !code.is_ascii() {
+        return Err("contains non-ASCII characters".to_string());
+    }
+    if code.len() != 13 {
+        return Err(format!("expected 13 digits, found {}", code.len()));
+    }
+    if !code.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("contains characters other than digits".to_string());
+    }
+    if !gtin13::check(code) {
+        let expected = gtin13::check_digit_for_prefix(&code[..12]).unwrap();
+        return Err(format!(
+            "check digit should be {}, found {}",
+            expected,
+            &code[12..]
+        ));
+    }
+
+    Ok(())
+}
+
+// Extract the value and span of the single string literal making up the
+// macro input. Escape sequences are rejected, since a GTIN never needs
+// them.
+fn string_literal(input: TokenStream) -> Result<(String, Span), (Span, String)> {
+    let mut tokens = input.into_iter();
+    let mut token = tokens.next();
+    // A literal passed through a `macro_rules!` fragment arrives wrapped
+    // in an invisible group.
+    while let Some(TokenTree::Group(group)) = token.clone() {
+        if group.delimiter() != Delimiter::None {
+            break;
+        }
+        let mut inner = group.stream().into_iter();
+        token = inner.next();
+        if inner.next().is_some() {
+            return Err((group.span(), "expected a string literal".to_string()));
+        }
+    }
+
+    let literal = match (token, tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal,
+        (Some(other), _) => return Err((other.span(), "expected a string literal".to_string())),
+        (None, _) => return Err((Span::call_site(), "expected a string literal".to_string())),
+    };
+
+    let span = literal.span();
+    let repr = literal.to_string();
+    let value = if let Some(raw) = repr.strip_prefix('r') {
+        let hashes = raw.chars().take_while(|&c| c == '#').count();
+        raw.get(hashes + 1..raw.len() - hashes - 1)
+    } else if repr.len() >= 2 && repr.starts_with('"') && repr.ends_with('"') {
+        let value = &repr[1..repr.len() - 1];
+        if value.contains('\\') {
+            return Err((
+                span,
+                "escape sequences are not allowed in GTIN literals".to_string(),
+            ));
+        }
+        Some(value)
+    } else {
+        None
+    };
+
+    match value {
+        Some(value) => Ok((value.to_string(), span)),
+        None => Err((span, "expected a string literal".to_string())),
+    }
+}
+
+// Build `compile_error!("message")` with every token at `span`, so the
+// error points at the offending input.
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    group.set_span(span);
+
+    vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ]
+    .into_iter()
+    .collect()
+}
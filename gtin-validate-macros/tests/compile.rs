@@ -0,0 +1,12 @@
+extern crate trybuild;
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass.rs");
+    t.compile_fail("tests/ui/check_digit.rs");
+    t.compile_fail("tests/ui/escape.rs");
+    t.compile_fail("tests/ui/length.rs");
+    t.compile_fail("tests/ui/non_numeric.rs");
+    t.compile_fail("tests/ui/not_a_string.rs");
+}
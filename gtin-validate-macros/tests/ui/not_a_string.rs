@@ -0,0 +1,7 @@
+extern crate gtin_validate_macros;
+
+use gtin_validate_macros::gtin13;
+
+fn main() {
+    let _ = gtin13!(4006381333931);
+}
@@ -0,0 +1,22 @@
+extern crate gtin_validate;
+extern crate gtin_validate_macros;
+
+use gtin_validate::Gtin13;
+use gtin_validate_macros::gtin13;
+
+macro_rules! wrapped {
+    ($code:expr) => {
+        gtin13!($code)
+    };
+}
+
+fn main() {
+    let code: Gtin13 = gtin13!("4006381333931");
+    assert_eq!(code.as_str(), "4006381333931");
+
+    let raw: Gtin13 = gtin13!(r#"1498279802125"#);
+    assert_eq!(raw.as_str(), "1498279802125");
+
+    let wrapped: Gtin13 = wrapped!("0036000291452");
+    assert_eq!(wrapped.as_str(), "0036000291452");
+}
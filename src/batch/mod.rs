@@ -0,0 +1,107 @@
+//! Validation of whole columns of codes of mixed widths, summarized for
+//! reporting.
+
+use gtin::{Gtin, GtinError, GtinKind};
+
+/// The outcome of validating a list of codes with
+/// [`validate_batch`](fn.validate_batch.html).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// The number of valid codes.
+    pub valid: usize,
+    /// The number of invalid codes.
+    pub invalid: usize,
+    /// The number of valid codes of each width, in the order GTIN-8,
+    /// GTIN-12, GTIN-13, GTIN-14.
+    pub by_kind: [usize; 4],
+    /// The index and error of every invalid code.
+    pub errors: Vec<(usize, GtinError)>,
+}
+
+/// Validate a list of codes of mixed widths, detecting the width of each
+/// code from its length, and summarize the results.
+///
+/// Each code is parsed as by [`Gtin::parse`](enum.Gtin.html#method.parse):
+/// whitespace is stripped from the left and right sides, but no
+/// zero-padding is performed.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{validate_batch, GtinError};
+///
+/// let summary = validate_batch(&["14567810", "036000291452", "4006381333931", "123"]);
+/// assert_eq!(summary.valid, 3);
+/// assert_eq!(summary.invalid, 1);
+/// assert_eq!(summary.by_kind, [1, 1, 1, 0]);
+/// assert_eq!(summary.errors, vec![(3, GtinError::TooShort { kind: None })]);
+/// ```
+pub fn validate_batch(codes: &[&str]) -> BatchSummary {
+    let mut summary = BatchSummary::default();
+
+    for (index, code) in codes.iter().enumerate() {
+        match Gtin::parse(code) {
+            Ok(gtin) => {
+                let slot = match gtin.kind() {
+                    GtinKind::Gtin8 => 0,
+                    GtinKind::Gtin12 => 1,
+                    GtinKind::Gtin13 => 2,
+                    GtinKind::Gtin14 => 3,
+                };
+                summary.valid += 1;
+                summary.by_kind[slot] += 1;
+            }
+            Err(err) => {
+                summary.invalid += 1;
+                summary.errors.push((index, err));
+            }
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_batch;
+    use super::BatchSummary;
+    use gtin::{GtinError, GtinKind};
+
+    #[test]
+    fn validate_batch_mixed() {
+        let summary = validate_batch(&[
+            "14567810",
+            " 036000291452\n",
+            "4006381333931",
+            "1498279802125",
+            "14567815983469",
+            "4006381333932",
+            "1234567890",
+            "❤",
+            "",
+        ]);
+        assert_eq!(summary.valid, 5);
+        assert_eq!(summary.invalid, 4);
+        assert_eq!(summary.by_kind, [1, 1, 2, 1]);
+        assert_eq!(
+            summary.errors,
+            vec![
+                (
+                    5,
+                    GtinError::CheckDigitIncorrect {
+                        kind: Some(GtinKind::Gtin13),
+                        expected: 1,
+                        found: 2,
+                    }
+                ),
+                (6, GtinError::InvalidLength),
+                (7, GtinError::NonAsciiString { kind: None }),
+                (8, GtinError::TooShort { kind: None }),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_batch_empty() {
+        assert_eq!(validate_batch(&[]), BatchSummary::default());
+    }
+}
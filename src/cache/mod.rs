@@ -0,0 +1,114 @@
+//! Memoized validation for workloads where the same codes recur,
+//! available with the `cache` feature.
+
+use std::cell::RefCell;
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use gtin13;
+
+/// A GTIN checker that remembers the results for recently seen codes.
+///
+/// Results are kept in a least-recently-used map of bounded capacity.
+/// Each entry stores a copy of the code, so the memory used grows up to
+/// roughly `capacity` times the size of a short `String` plus the map
+/// overhead.
+///
+/// `check` itself is only a few nanoseconds, and a cache lookup hashes
+/// the code and updates the recency list, which costs about as much. The
+/// cache is therefore only worthwhile for heavily skewed distributions,
+/// where a small set of hot codes makes up most of the input; measure
+/// your workload (see the `cache` bench) before adopting it.
+///
+/// The checker uses interior mutability and is not `Sync`; use one per
+/// thread.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::CachedChecker;
+///
+/// let checker = CachedChecker::new(1024);
+/// assert_eq!(checker.check13("4006381333931"), true);
+/// assert_eq!(checker.check13("4006381333931"), true); // Served from the cache
+/// assert_eq!(checker.check13("4006381333932"), false);
+/// ```
+#[derive(Debug)]
+pub struct CachedChecker {
+    gtin13: RefCell<LruCache<String, bool>>,
+}
+
+impl CachedChecker {
+    /// Create a checker remembering up to `capacity` codes. A capacity of
+    /// zero is treated as one.
+    pub fn new(capacity: usize) -> CachedChecker {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        CachedChecker {
+            gtin13: RefCell::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Check a GTIN-13 code, as [`gtin13::check`](gtin13/fn.check.html)
+    /// does, reusing the result if the code was seen recently.
+    pub fn check13(&self, code: &str) -> bool {
+        let mut cache = self.gtin13.borrow_mut();
+        if let Some(&valid) = cache.get(code) {
+            return valid;
+        }
+
+        let valid = gtin13::check(code);
+        cache.put(code.to_string(), valid);
+        valid
+    }
+
+    /// Return the number of codes currently remembered.
+    pub fn len(&self) -> usize {
+        self.gtin13.borrow().len()
+    }
+
+    /// Return true if no codes are remembered.
+    pub fn is_empty(&self) -> bool {
+        self.gtin13.borrow().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedChecker;
+    use gtin13;
+
+    #[test]
+    fn check13_matches_check() {
+        let checker = CachedChecker::new(2);
+        for code in &["4006381333931", "4006381333932", "", "❤", "4006381333931"] {
+            assert_eq!(checker.check13(code), gtin13::check(code));
+        }
+    }
+
+    #[test]
+    fn capacity_is_bounded() {
+        let checker = CachedChecker::new(2);
+        assert!(checker.is_empty());
+        checker.check13("4006381333931");
+        checker.check13("1498279802125");
+        checker.check13("0000000000000");
+        checker.check13("0000000000000");
+        assert_eq!(checker.len(), 2);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let checker = CachedChecker::new(0);
+        assert_eq!(checker.check13("4006381333931"), true);
+        assert_eq!(checker.len(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn check13_doesnt_crash(ref s in ".*") {
+            let checker = CachedChecker::new(4);
+            assert_eq!(checker.check13(s), gtin13::check(s));
+            assert_eq!(checker.check13(s), gtin13::check(s));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Streaming correction of a column of codes, available with the `io`
+//! feature.
+
+use std::io;
+use std::io::{BufRead, Write};
+
+use gtin::GtinKind;
+use gtin12;
+use gtin13;
+use gtin14;
+use gtin8;
+
+/// Counts of the rows processed by [`clean_column`](fn.clean_column.html).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CleanStats {
+    /// Rows that were already valid and were written unchanged.
+    pub already_valid: usize,
+    /// Rows that were invalid but could be fixed.
+    pub fixed: usize,
+    /// Rows that could not be fixed and were written as blank lines.
+    pub failed: usize,
+}
+
+/// Fix a column of codes of the given kind, one code per line.
+///
+/// Each line of `input` is passed to the `fix` function of the module
+/// for `kind`, and the corrected code is written to `output` on its own
+/// line. Lines that cannot be fixed, including blank lines, are written
+/// as blank lines, so the output keeps one line per input line and can
+/// be pasted back next to the original column.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{clean_column, CleanStats, GtinKind};
+///
+/// let input = "036000291452\n36000291452 \nSKU-1234\n";
+/// let mut output = Vec::new();
+/// let stats = clean_column(input.as_bytes(), &mut output, GtinKind::Gtin12).unwrap();
+///
+/// assert_eq!(output, b"036000291452\n036000291452\n\n");
+/// assert_eq!(
+///     stats,
+///     CleanStats { already_valid: 1, fixed: 1, failed: 1 }
+/// );
+/// ```
+pub fn clean_column<R: BufRead, W: Write>(
+    input: R,
+    mut output: W,
+    kind: GtinKind,
+) -> io::Result<CleanStats> {
+    let mut stats = CleanStats::default();
+
+    for line in input.lines() {
+        let line = line?;
+        let fixed = if line.trim().is_empty() {
+            None
+        } else {
+            match kind {
+                GtinKind::Gtin8 => gtin8::fix(&line).ok(),
+                GtinKind::Gtin12 => gtin12::fix(&line).ok(),
+                GtinKind::Gtin13 => gtin13::fix(&line).ok(),
+                GtinKind::Gtin14 => gtin14::fix(&line).ok(),
+            }
+        };
+
+        match fixed {
+            Some(code) => {
+                if code == line {
+                    stats.already_valid += 1;
+                } else {
+                    stats.fixed += 1;
+                }
+                writeln!(output, "{}", code)?;
+            }
+            None => {
+                stats.failed += 1;
+                writeln!(output)?;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clean_column;
+    use super::CleanStats;
+    use gtin::GtinKind;
+
+    #[test]
+    fn clean_column_mixed_rows() {
+        let input = "4006381333931\r\n 495205944325\n\n4006381333932\n  \n";
+        let mut output = Vec::new();
+        let stats = clean_column(input.as_bytes(), &mut output, GtinKind::Gtin13).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "4006381333931\n0495205944325\n\n\n\n"
+        );
+        assert_eq!(
+            stats,
+            CleanStats {
+                already_valid: 1,
+                fixed: 1,
+                failed: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn clean_column_empty_input() {
+        let mut output = Vec::new();
+        let stats = clean_column(&b""[..], &mut output, GtinKind::Gtin8).unwrap();
+        assert!(output.is_empty());
+        assert_eq!(stats, CleanStats::default());
+    }
+
+    #[test]
+    fn clean_column_invalid_utf8() {
+        let mut output = Vec::new();
+        assert!(clean_column(&b"\xff\n"[..], &mut output, GtinKind::Gtin14).is_err());
+    }
+}
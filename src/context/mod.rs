@@ -0,0 +1,103 @@
+//! Errors that keep the input they were produced from.
+
+/// An error together with the input that caused it, as returned by the
+/// `fix_with_input` function of each GTIN module.
+///
+/// The `FixError` types are small `Copy` values so that they cost
+/// nothing to return and compare. `InputError` pairs one with the
+/// offending input, for errors that travel far from the row they came
+/// from, such as in the log of a batch job.
+///
+/// # The `error-input` feature
+/// Keeping the input costs an allocation and a copy per error, so it is
+/// only done with the `error-input` feature. Without it, `fix_with_input`
+/// behaves like `fix` and [`input`](#method.input) always returns
+/// `None`. The copy is only made when an error occurs, and never for
+/// input longer than the `MAX_INPUT_LEN` of the module. The limit applies
+/// to the input as passed, before whitespace is trimmed, so it bounds the
+/// copy even for input that is mostly padding; `input` returns `None` for
+/// such input.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13::{self, FixError};
+///
+/// let err = gtin13::fix_with_input(" 4006381333932\n").unwrap_err();
+/// assert_eq!(
+///     *err.error(),
+///     FixError::CheckDigitIncorrect { expected: 1, found: 2 }
+/// );
+/// #[cfg(feature = "error-input")]
+/// assert_eq!(err.input(), Some("4006381333932"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputError<E> {
+    error: E,
+    input: Option<String>,
+}
+
+impl<E> InputError<E> {
+    // Keep a trimmed copy of `input` if the `error-input` feature is
+    // enabled and the untrimmed input is no longer than `max_len` bytes.
+    pub(crate) fn new(error: E, input: &str, max_len: usize) -> InputError<E> {
+        let input = if cfg!(feature = "error-input") && input.len() <= max_len {
+            Some(input.trim().to_string())
+        } else {
+            None
+        };
+
+        InputError { error, input }
+    }
+
+    /// Return the error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Return the input that caused the error, after any whitespace
+    /// trimming done by the function returning the error, if it was
+    /// kept. See [the `error-input` feature](#the-error-input-feature).
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    /// Discard the input, returning only the error.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputError;
+
+    #[test]
+    fn new_keeps_input_with_feature() {
+        let err = InputError::new((), "4006381333932", 13);
+        if cfg!(feature = "error-input") {
+            assert_eq!(err.input(), Some("4006381333932"));
+        } else {
+            assert_eq!(err.input(), None);
+        }
+    }
+
+    #[test]
+    fn new_skips_long_input() {
+        let err = InputError::new((), "40063813339310", 13);
+        assert_eq!(err.input(), None);
+        assert_eq!(err.into_error(), ());
+    }
+
+    #[test]
+    fn new_measures_untrimmed_input() {
+        let err = InputError::new((), " 4006381333932 ", 13);
+        assert_eq!(err.input(), None);
+
+        let err = InputError::new((), " 4006381333932 ", 15);
+        if cfg!(feature = "error-input") {
+            assert_eq!(err.input(), Some("4006381333932"));
+        } else {
+            assert_eq!(err.input(), None);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Extraction of GTINs from, and generation of, GS1 Digital Link URLs.
+
+use gtin::{Gtin, GtinError};
+
+/// Extract and validate the GTIN of a GS1 Digital Link URL.
+///
+/// The GTIN is the path segment following the first `01` segment, as in
+/// `https://id.gs1.org/01/09506000134352`. Any path before the `01` key,
+/// later key qualifiers such as `/10/<batch>`, and the query string and
+/// fragment are ignored. The GTIN may be of any width and is parsed as
+/// by [`Gtin::parse`](enum.Gtin.html#method.parse).
+///
+/// A URL without an `01` key gives `MissingGtin`; an invalid GTIN gives
+/// the error from `Gtin::parse`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{from_digital_link, Gtin, GtinError};
+///
+/// assert_eq!(
+///     from_digital_link("https://id.gs1.org/01/09506000134352"),
+///     Ok(Gtin::Gtin14("09506000134352".parse().unwrap()))
+/// );
+/// assert_eq!(
+///     from_digital_link("https://id.gs1.org/00/106141412345678908"),
+///     Err(GtinError::MissingGtin)
+/// );
+/// ```
+pub fn from_digital_link(url: &str) -> Result<Gtin, GtinError> {
+    let url = url.split(&['?', '#'][..]).next().unwrap_or("");
+    let path = match url.find("://") {
+        Some(start) => {
+            let rest = &url[start + 3..];
+            match rest.find('/') {
+                Some(path_start) => &rest[path_start..],
+                None => "",
+            }
+        }
+        None => return Err(GtinError::MissingGtin),
+    };
+
+    let mut segments = path.split('/');
+    while let Some(segment) = segments.next() {
+        if segment == "01" {
+            return match segments.next() {
+                Some(code) => Gtin::parse(code),
+                None => Err(GtinError::MissingGtin),
+            };
+        }
+    }
+
+    Err(GtinError::MissingGtin)
+}
+
+/// Build a GS1 Digital Link URL for a GTIN, of the form
+/// `https://{domain}/01/{gtin14}`.
+///
+/// The code may be of any width and is parsed as by
+/// [`Gtin::parse`](enum.Gtin.html#method.parse), then zero-padded to a
+/// GTIN-14 as Digital Link requires. An empty `domain` means GS1's own
+/// resolver, `id.gs1.org`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{from_digital_link, to_digital_link};
+///
+/// let url = to_digital_link("4006381333931", "").unwrap();
+/// assert_eq!(url, "https://id.gs1.org/01/04006381333931");
+/// assert_eq!(from_digital_link(&url).unwrap().as_str(), "04006381333931");
+///
+/// assert!(to_digital_link("4006381333932", "example.com").is_err());
+/// ```
+pub fn to_digital_link(code: &str, domain: &str) -> Result<String, GtinError> {
+    let gtin14 = Gtin::parse(code)?.to_gtin14();
+    let domain = if domain.is_empty() {
+        "id.gs1.org"
+    } else {
+        domain
+    };
+
+    Ok(format!("https://{}/01/{}", domain, gtin14))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_digital_link;
+    use super::to_digital_link;
+    use gtin::{Gtin, GtinError, GtinKind};
+
+    #[test]
+    fn from_digital_link_valid() {
+        assert_eq!(
+            from_digital_link("https://id.gs1.org/01/09506000134352"),
+            Ok(Gtin::Gtin14("09506000134352".parse().unwrap()))
+        );
+        assert_eq!(
+            from_digital_link("https://example.com/01/09506000134352/10/ABC123?17=201225"),
+            Ok(Gtin::Gtin14("09506000134352".parse().unwrap()))
+        );
+        assert_eq!(
+            from_digital_link("http://example.com/shop/01/4006381333931#details"),
+            Ok(Gtin::Gtin13("4006381333931".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn from_digital_link_missing_key() {
+        assert_eq!(
+            from_digital_link("https://id.gs1.org/00/106141412345678908"),
+            Err(GtinError::MissingGtin)
+        );
+        assert_eq!(
+            from_digital_link("https://id.gs1.org/01"),
+            Err(GtinError::MissingGtin)
+        );
+        assert_eq!(
+            from_digital_link("https://id.gs1.org"),
+            Err(GtinError::MissingGtin)
+        );
+        assert_eq!(
+            from_digital_link("id.gs1.org/01/09506000134352"),
+            Err(GtinError::MissingGtin)
+        );
+        // The key is in the query string, not the path
+        assert_eq!(
+            from_digital_link("https://example.com/?01=09506000134352"),
+            Err(GtinError::MissingGtin)
+        );
+        assert_eq!(from_digital_link(""), Err(GtinError::MissingGtin));
+    }
+
+    #[test]
+    fn from_digital_link_invalid_gtin() {
+        assert_eq!(
+            from_digital_link("https://id.gs1.org/01/09506000134353"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin14),
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            from_digital_link("https://id.gs1.org/01/"),
+            Err(GtinError::TooShort { kind: None })
+        );
+        assert_eq!(
+            from_digital_link("https://id.gs1.org/01/0950600013435X"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14)
+            })
+        );
+    }
+
+    #[test]
+    fn to_digital_link_widths() {
+        assert_eq!(
+            to_digital_link("14567810", "").unwrap(),
+            "https://id.gs1.org/01/00000014567810"
+        );
+        assert_eq!(
+            to_digital_link(" 036000291452\n", "example.com").unwrap(),
+            "https://example.com/01/00036000291452"
+        );
+        assert_eq!(
+            to_digital_link("09506000134352", "id.gs1.org").unwrap(),
+            "https://id.gs1.org/01/09506000134352"
+        );
+    }
+
+    #[test]
+    fn to_digital_link_invalid() {
+        assert_eq!(
+            to_digital_link("4006381333932", ""),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            to_digital_link("1234567890", ""),
+            Err(GtinError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn to_digital_link_round_trip() {
+        for code in &[
+            "14567810",
+            "036000291452",
+            "4006381333931",
+            "09506000134352",
+        ] {
+            let gtin = Gtin::parse(code).unwrap();
+            let url = to_digital_link(code, "").unwrap();
+            assert_eq!(
+                from_digital_link(&url),
+                Ok(Gtin::Gtin14(gtin.to_gtin14().parse().unwrap()))
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn from_digital_link_doesnt_crash(ref s in ".*") {
+            let _ = from_digital_link(s);
+        }
+
+        #[test]
+        fn to_digital_link_round_trips(ref s in "[0-9]{13}") {
+            if let Ok(url) = to_digital_link(s, "example.com") {
+                let gtin = from_digital_link(&url).unwrap();
+                assert_eq!(gtin.to_gtin14(), format!("0{}", s));
+            }
+        }
+    }
+}
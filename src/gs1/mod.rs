@@ -0,0 +1,234 @@
+//! Lookup of the GS1 member organization that issued a GTIN prefix.
+
+use normalize::normalize_to_gtin14;
+
+/// GS1 prefix ranges and the member organization or special use they
+/// are assigned to.
+///
+/// Each entry is `(start, end, name)`, where `start` and `end` are the
+/// inclusive bounds of a range of three-digit GS1 prefixes, that is the
+/// first three digits of the GTIN-13 form of a code. Entries are sorted
+/// by `start` and do not overlap. Prefixes that are not covered by any
+/// entry are currently unassigned.
+pub const GS1_PREFIX_TABLE: &[(u16, u16, &str)] = &[
+    (0, 19, "United States"),
+    (20, 29, "Restricted distribution"),
+    (30, 39, "United States"),
+    (40, 49, "Restricted distribution"),
+    (50, 59, "Coupons"),
+    (60, 139, "United States"),
+    (200, 299, "Restricted distribution"),
+    (300, 379, "France"),
+    (380, 380, "Bulgaria"),
+    (383, 383, "Slovenia"),
+    (385, 385, "Croatia"),
+    (387, 387, "Bosnia and Herzegovina"),
+    (389, 389, "Montenegro"),
+    (390, 390, "Kosovo"),
+    (400, 440, "Germany"),
+    (450, 459, "Japan"),
+    (460, 469, "Russia"),
+    (470, 470, "Kyrgyzstan"),
+    (471, 471, "Taiwan"),
+    (474, 474, "Estonia"),
+    (475, 475, "Latvia"),
+    (476, 476, "Azerbaijan"),
+    (477, 477, "Lithuania"),
+    (478, 478, "Uzbekistan"),
+    (479, 479, "Sri Lanka"),
+    (480, 480, "Philippines"),
+    (481, 481, "Belarus"),
+    (482, 482, "Ukraine"),
+    (483, 483, "Turkmenistan"),
+    (484, 484, "Moldova"),
+    (485, 485, "Armenia"),
+    (486, 486, "Georgia"),
+    (487, 487, "Kazakhstan"),
+    (488, 488, "Tajikistan"),
+    (489, 489, "Hong Kong"),
+    (490, 499, "Japan"),
+    (500, 509, "United Kingdom"),
+    (520, 521, "Greece"),
+    (528, 528, "Lebanon"),
+    (529, 529, "Cyprus"),
+    (530, 530, "Albania"),
+    (531, 531, "North Macedonia"),
+    (535, 535, "Malta"),
+    (539, 539, "Ireland"),
+    (540, 549, "Belgium and Luxembourg"),
+    (560, 560, "Portugal"),
+    (569, 569, "Iceland"),
+    (570, 579, "Denmark"),
+    (590, 590, "Poland"),
+    (594, 594, "Romania"),
+    (599, 599, "Hungary"),
+    (600, 601, "South Africa"),
+    (603, 603, "Ghana"),
+    (604, 604, "Senegal"),
+    (608, 608, "Bahrain"),
+    (609, 609, "Mauritius"),
+    (611, 611, "Morocco"),
+    (613, 613, "Algeria"),
+    (615, 615, "Nigeria"),
+    (616, 616, "Kenya"),
+    (617, 617, "Cameroon"),
+    (618, 618, "Côte d'Ivoire"),
+    (619, 619, "Tunisia"),
+    (620, 620, "Tanzania"),
+    (621, 621, "Syria"),
+    (622, 622, "Egypt"),
+    (623, 623, "Brunei"),
+    (624, 624, "Libya"),
+    (625, 625, "Jordan"),
+    (626, 626, "Iran"),
+    (627, 627, "Kuwait"),
+    (628, 628, "Saudi Arabia"),
+    (629, 629, "United Arab Emirates"),
+    (630, 630, "Qatar"),
+    (631, 631, "Namibia"),
+    (640, 649, "Finland"),
+    (690, 699, "China"),
+    (700, 709, "Norway"),
+    (729, 729, "Israel"),
+    (730, 739, "Sweden"),
+    (740, 740, "Guatemala"),
+    (741, 741, "El Salvador"),
+    (742, 742, "Honduras"),
+    (743, 743, "Nicaragua"),
+    (744, 744, "Costa Rica"),
+    (745, 745, "Panama"),
+    (746, 746, "Dominican Republic"),
+    (750, 750, "Mexico"),
+    (754, 755, "Canada"),
+    (759, 759, "Venezuela"),
+    (760, 769, "Switzerland and Liechtenstein"),
+    (770, 771, "Colombia"),
+    (773, 773, "Uruguay"),
+    (775, 775, "Peru"),
+    (777, 777, "Bolivia"),
+    (778, 779, "Argentina"),
+    (780, 780, "Chile"),
+    (784, 784, "Paraguay"),
+    (786, 786, "Ecuador"),
+    (789, 790, "Brazil"),
+    (800, 839, "Italy"),
+    (840, 849, "Spain"),
+    (850, 850, "Cuba"),
+    (858, 858, "Slovakia"),
+    (859, 859, "Czech Republic"),
+    (860, 860, "Serbia"),
+    (865, 865, "Mongolia"),
+    (867, 867, "North Korea"),
+    (868, 869, "Türkiye"),
+    (870, 879, "Netherlands"),
+    (880, 880, "South Korea"),
+    (883, 883, "Myanmar"),
+    (884, 884, "Cambodia"),
+    (885, 885, "Thailand"),
+    (888, 888, "Singapore"),
+    (890, 890, "India"),
+    (893, 893, "Vietnam"),
+    (896, 896, "Pakistan"),
+    (899, 899, "Indonesia"),
+    (900, 919, "Austria"),
+    (930, 939, "Australia"),
+    (940, 949, "New Zealand"),
+    (950, 951, "GS1 Global Office"),
+    (955, 955, "Malaysia"),
+    (958, 958, "Macau"),
+    (960, 969, "GS1 Global Office"),
+    (977, 977, "Serial publications (ISSN)"),
+    (978, 979, "Bookland (ISBN)"),
+    (980, 980, "Refund receipts"),
+    (981, 984, "Coupons"),
+    (990, 999, "Coupons"),
+];
+
+/// Look up the GS1 member organization or special use assigned to the
+/// prefix of a GTIN-12, GTIN-13 or GTIN-14 code.
+///
+/// The code is fixed and normalized to GTIN-14 first, and the prefix is
+/// read from its GTIN-13 form (skipping the packaging indicator of a
+/// GTIN-14). The name is taken from
+/// [`GS1_PREFIX_TABLE`](constant.GS1_PREFIX_TABLE.html).
+///
+/// `None` is returned for invalid codes, unassigned prefixes, and
+/// zero-padded GTIN-8 codes (those whose GTIN-13 form begins with five
+/// zeros), because GTIN-8 prefixes are assigned separately.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gs1_prefix_region;
+///
+/// assert_eq!(gs1_prefix_region("4006381333931"), Some("Germany"));
+/// assert_eq!(gs1_prefix_region("036000291452"), Some("United States"));
+/// assert_eq!(gs1_prefix_region("9780131103627"), Some("Bookland (ISBN)"));
+/// assert_eq!(gs1_prefix_region("4006381333932"), None); // Bad check digit
+/// ```
+pub fn gs1_prefix_region(code: &str) -> Option<&'static str> {
+    let gtin = normalize_to_gtin14(code).ok()?;
+    if gtin[1..6].bytes().all(|b| b == b'0') {
+        return None;
+    }
+
+    let prefix = gtin[1..4]
+        .bytes()
+        .fold(0, |acc, b| acc * 10 + u16::from(b - b'0'));
+    GS1_PREFIX_TABLE
+        .iter()
+        .find(|&&(start, end, _)| start <= prefix && prefix <= end)
+        .map(|&(_, _, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gs1_prefix_region;
+    use super::GS1_PREFIX_TABLE;
+
+    #[test]
+    fn table_sorted_and_disjoint() {
+        for &(start, end, _) in GS1_PREFIX_TABLE {
+            assert!(start <= end);
+            assert!(end <= 999);
+        }
+        for pair in GS1_PREFIX_TABLE.windows(2) {
+            assert!(pair[0].1 < pair[1].0);
+        }
+    }
+
+    #[test]
+    fn region_static_data() {
+        assert_eq!(gs1_prefix_region("4006381333931"), Some("Germany"));
+        assert_eq!(gs1_prefix_region("5000000000005"), Some("United Kingdom"));
+        assert_eq!(
+            gs1_prefix_region("9770000000003"),
+            Some("Serial publications (ISSN)")
+        );
+        assert_eq!(
+            gs1_prefix_region("2000000000008"),
+            Some("Restricted distribution")
+        );
+    }
+
+    #[test]
+    fn region_widths() {
+        assert_eq!(gs1_prefix_region("036000291452"), Some("United States"));
+        assert_eq!(gs1_prefix_region("0036000291452"), Some("United States"));
+        assert_eq!(gs1_prefix_region("14006381333938"), Some("Germany"));
+    }
+
+    #[test]
+    fn region_boundaries() {
+        assert_eq!(gs1_prefix_region("3800000000003"), Some("Bulgaria"));
+        assert_eq!(gs1_prefix_region("3810000000002"), None);
+        assert_eq!(gs1_prefix_region("4400000000004"), Some("Germany"));
+        assert_eq!(gs1_prefix_region("4410000000003"), None);
+    }
+
+    #[test]
+    fn region_invalid() {
+        assert_eq!(gs1_prefix_region("4006381333932"), None);
+        assert_eq!(gs1_prefix_region("14567810"), None); // GTIN-8
+        assert_eq!(gs1_prefix_region("❤"), None);
+    }
+}
@@ -0,0 +1,1293 @@
+//! A single type covering validated GTIN codes of every supported width.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use gtin12;
+use gtin13;
+use gtin14;
+use gtin8;
+use utils;
+
+/// The width of a GTIN code.
+///
+/// `Display` prints the conventional name of the width, such as
+/// `GTIN-13`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GtinKind {
+    /// GTIN-8 (EAN-8), 8 digits.
+    Gtin8,
+    /// GTIN-12 (UPC-A), 12 digits.
+    Gtin12,
+    /// GTIN-13 (EAN-13), 13 digits.
+    Gtin13,
+    /// GTIN-14, 14 digits.
+    Gtin14,
+}
+
+impl GtinKind {
+    /// Return the number of digits in a code of this kind.
+    pub fn length(self) -> usize {
+        match self {
+            GtinKind::Gtin8 => 8,
+            GtinKind::Gtin12 => 12,
+            GtinKind::Gtin13 => 13,
+            GtinKind::Gtin14 => 14,
+        }
+    }
+
+    /// Guess the kind of a code from its length alone.
+    ///
+    /// Whitespace is stripped from the left and right sides, then the
+    /// length is matched against the GTIN widths. The content is not
+    /// inspected, so the code may still contain non-digits or have an
+    /// incorrect check digit. Lengths that match no width (anything other
+    /// than 8, 12, 13 or 14) return `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::GtinKind;
+    ///
+    /// assert_eq!(GtinKind::detect("4006381333932"), Some(GtinKind::Gtin13));
+    /// assert_eq!(GtinKind::detect(" 14567810 "), Some(GtinKind::Gtin8));
+    /// assert_eq!(GtinKind::detect("1234567890"), None);
+    /// ```
+    pub fn detect(code: &str) -> Option<GtinKind> {
+        match code.trim().len() {
+            8 => Some(GtinKind::Gtin8),
+            12 => Some(GtinKind::Gtin12),
+            13 => Some(GtinKind::Gtin13),
+            14 => Some(GtinKind::Gtin14),
+            _ => None,
+        }
+    }
+
+    /// Detect the kind of a code as [`detect`](#method.detect) does, but
+    /// only return it if the trimmed code also passes the `check`
+    /// function for that kind.
+    ///
+    /// Together with `detect`, this distinguishes a code that "looks like
+    /// a GTIN-13 but the check digit fails" from one that is not shaped
+    /// like a GTIN at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::GtinKind;
+    ///
+    /// assert_eq!(GtinKind::detect_valid("4006381333931"), Some(GtinKind::Gtin13));
+    /// assert_eq!(GtinKind::detect_valid("4006381333932"), None); // Bad check digit
+    /// ```
+    pub fn detect_valid(code: &str) -> Option<GtinKind> {
+        let code = code.trim();
+        let kind = GtinKind::detect(code)?;
+        let valid = match kind {
+            GtinKind::Gtin8 => gtin8::check(code),
+            GtinKind::Gtin12 => gtin12::check(code),
+            GtinKind::Gtin13 => gtin13::check(code),
+            GtinKind::Gtin14 => gtin14::check(code),
+        };
+        if valid {
+            Some(kind)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for GtinKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            GtinKind::Gtin8 => "GTIN-8",
+            GtinKind::Gtin12 => "GTIN-12",
+            GtinKind::Gtin13 => "GTIN-13",
+            GtinKind::Gtin14 => "GTIN-14",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Errors from the crate-level functions, covering every GTIN width.
+///
+/// The variants match those of the per-module `FixError` types, plus
+/// `InvalidLength` for input that matches no width, `MissingGtin` for
+/// input with no code to validate, and `WrongKind` for input of a
+/// different width than required. Each variant other than these three
+/// records in `kind` the width the code was being
+/// validated as, or `None` if the error occurred before a width could be
+/// chosen. The `FixError` of any module converts into `GtinError` with
+/// `From`/`Into`, setting `kind` to that module's width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GtinError {
+    /// The provided string contains non-ASCII characters.
+    NonAsciiString {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+    },
+    /// The provided string contains characters other than ASCII digits.
+    NonNumericString {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+    },
+    /// The provided code was too long: longer than `kind`, or than the
+    /// widest GTIN (14 digits) if no width was chosen.
+    TooLong {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+        /// The length of the code in bytes, after any whitespace
+        /// trimming done by the function returning the error. A length
+        /// within the expected width means that the input was rejected
+        /// only because of its surrounding whitespace.
+        trimmed_len: usize,
+    },
+    /// The provided code was too short: shorter than `kind`, or than the
+    /// narrowest GTIN (8 digits) if no width was chosen.
+    TooShort {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+    },
+    /// The length of the provided code lies between two GTIN widths.
+    InvalidLength,
+    /// The input does not contain a GTIN where one was expected, such as
+    /// a GS1 Digital Link URL without the `01` key.
+    MissingGtin,
+    /// The provided code has the length of a different width than the
+    /// one required, such as a 12-digit UPC-A where a GTIN-13 is expected.
+    WrongKind {
+        /// The width the code was required to have.
+        expected: GtinKind,
+        /// The length of the code in bytes, after whitespace trimming.
+        actual_len: usize,
+    },
+    /// The calculated check-digit did not match the code's check-digit.
+    CheckDigitIncorrect {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+        /// The check digit computed from the other digits of the code.
+        expected: u8,
+        /// The check digit the code ends with.
+        found: u8,
+    },
+    /// More than one interpretation of the code is possible, so none was
+    /// chosen.
+    Ambiguous {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+    },
+    /// The code is valid, but cannot be used for or converted into the
+    /// requested form.
+    NotRepresentable {
+        /// The width being validated, if known.
+        kind: Option<GtinKind>,
+    },
+}
+
+impl GtinError {
+    /// Return the width the code was being validated as, if known.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::{Gtin, GtinKind};
+    ///
+    /// let err = Gtin::parse("4006381333932").unwrap_err();
+    /// assert_eq!(err.kind(), Some(GtinKind::Gtin13));
+    /// assert_eq!(Gtin::parse("400638133").unwrap_err().kind(), None);
+    /// ```
+    pub fn kind(&self) -> Option<GtinKind> {
+        match *self {
+            GtinError::NonAsciiString { kind }
+            | GtinError::NonNumericString { kind }
+            | GtinError::TooLong { kind, .. }
+            | GtinError::TooShort { kind }
+            | GtinError::CheckDigitIncorrect { kind, .. }
+            | GtinError::Ambiguous { kind }
+            | GtinError::NotRepresentable { kind } => kind,
+            GtinError::WrongKind { expected, .. } => Some(expected),
+            GtinError::InvalidLength | GtinError::MissingGtin => None,
+        }
+    }
+}
+
+// Convert each module's `FixError`, recording the module's width.
+macro_rules! impl_gtin_error_from {
+    ($($module:ident => $kind:ident),+) => {
+        $(
+            impl From<$module::FixError> for GtinError {
+                fn from(err: $module::FixError) -> GtinError {
+                    let kind = Some(GtinKind::$kind);
+                    match err {
+                        $module::FixError::NonAsciiString => GtinError::NonAsciiString { kind },
+                        $module::FixError::NonNumericString => {
+                            GtinError::NonNumericString { kind }
+                        }
+                        $module::FixError::TooLong { trimmed_len } => {
+                            GtinError::TooLong { kind, trimmed_len }
+                        }
+                        $module::FixError::TooShort => GtinError::TooShort { kind },
+                        $module::FixError::CheckDigitIncorrect { expected, found } => {
+                            GtinError::CheckDigitIncorrect {
+                                kind,
+                                expected,
+                                found,
+                            }
+                        }
+                        $module::FixError::Ambiguous => GtinError::Ambiguous { kind },
+                        $module::FixError::NotRepresentable => {
+                            GtinError::NotRepresentable { kind }
+                        }
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_gtin_error_from!(gtin8 => Gtin8, gtin12 => Gtin12, gtin13 => Gtin13, gtin14 => Gtin14);
+
+/// A validated GTIN code of any of the supported widths.
+///
+/// Each variant holds the validated code of its width, such as a
+/// [`Gtin13`](gtin13/struct.Gtin13.html), so a `Gtin` can only be built
+/// from a valid code. Two values
+/// are equal only when both the variant and the string match, so a
+/// GTIN-12 and the zero-padded GTIN-13 form of the same code are *not*
+/// equal. `Gtin` implements `Hash` and `Eq`, so it can be used directly
+/// as a `HashMap` key or `HashSet` element.
+///
+/// To compare codes regardless of width, use
+/// [`same_product`](#method.same_product), or key collections by
+/// [`canonical_key`](#method.canonical_key).
+///
+/// # Ordering
+/// `Gtin` has a total order, so it can also key a `BTreeMap` or
+/// `BTreeSet` for deterministic iteration. Codes are ordered first by
+/// kind, from narrowest to widest (GTIN-8, GTIN-12, GTIN-13, GTIN-14),
+/// and then by their digits. All codes of one kind have the same length,
+/// so the digits compare in numeric order. Codes of different kinds are
+/// never interleaved, even when they refer to the same product.
+///
+/// # Serialization
+/// With the `serde` feature, a `Gtin` is serialized as an object holding
+/// the variant name and the code, so the width survives a round trip:
+///
+/// ```json
+/// { "kind": "Gtin13", "code": "4006381333931" }
+/// ```
+///
+/// This shape is part of the stable API. When deserializing, a string
+/// code must be exactly the digits of a valid code of the named kind; it
+/// is not trimmed or padded. The code may also be a JSON integer, as
+/// written by systems that store GTINs as numbers and so drop their
+/// leading zeros: it is zero-padded to the width of the named kind, and
+/// rejected if it has more digits than that width.
+///
+/// ```json
+/// { "kind": "Gtin12", "code": 87248795257 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "code"))]
+pub enum Gtin {
+    // The derived `Ord` compares variants in declaration order, which is
+    // part of the documented ordering; keep them from narrowest to widest.
+    /// A GTIN-8 (EAN-8) code.
+    Gtin8(gtin8::Gtin8),
+    /// A GTIN-12 (UPC-A) code.
+    Gtin12(gtin12::Gtin12),
+    /// A GTIN-13 (EAN-13) code.
+    Gtin13(gtin13::Gtin13),
+    /// A GTIN-14 code.
+    Gtin14(gtin14::Gtin14),
+}
+
+impl Gtin {
+    /// Parse a GTIN, detecting the width from the length of the code.
+    ///
+    /// Whitespace is stripped from the left and right sides, but no
+    /// zero-padding is performed, because a shortened code could belong
+    /// to more than one width.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::{Gtin, GtinError};
+    ///
+    /// assert_eq!(
+    ///     Gtin::parse("897854613315"),
+    ///     Ok(Gtin::Gtin12("897854613315".parse().unwrap()))
+    /// );
+    /// assert_eq!(Gtin::parse("8978546133"), Err(GtinError::InvalidLength));
+    /// ```
+    pub fn parse(code: &str) -> Result<Gtin, GtinError> {
+        let code = code.trim();
+        if !code.is_ascii() {
+            return Err(GtinError::NonAsciiString { kind: None });
+        }
+
+        match code.len() {
+            8 => Ok(Gtin::from_valid(GtinKind::Gtin8, gtin8::fix(code)?)),
+            12 => Ok(Gtin::from_valid(GtinKind::Gtin12, gtin12::fix(code)?)),
+            13 => Ok(Gtin::from_valid(GtinKind::Gtin13, gtin13::fix(code)?)),
+            14 => Ok(Gtin::from_valid(GtinKind::Gtin14, gtin14::fix(code)?)),
+            len if len > 14 => Err(GtinError::TooLong {
+                kind: None,
+                trimmed_len: len,
+            }),
+            len if len < 8 => Err(GtinError::TooShort { kind: None }),
+            _ => Err(GtinError::InvalidLength),
+        }
+    }
+
+    /// Parse a GTIN that must be of the given width.
+    ///
+    /// Whitespace is stripped from the left and right sides, and no
+    /// zero-padding is performed. Unlike [`parse`](#method.parse), which
+    /// accepts any width, a code whose length is not that of `kind` is
+    /// rejected with `WrongKind`, even if it is a valid code of another
+    /// width. This lets import tooling report a value such as a UPC-A in
+    /// a GTIN-13 column as being of the wrong type rather than merely
+    /// invalid. A code of the right length is validated with the `fix`
+    /// function of its width.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::{Gtin, GtinError, GtinKind};
+    ///
+    /// assert_eq!(
+    ///     Gtin::parse_exact("4006381333931", GtinKind::Gtin13),
+    ///     Ok(Gtin::Gtin13("4006381333931".parse().unwrap()))
+    /// );
+    /// assert_eq!(
+    ///     Gtin::parse_exact("036000291452", GtinKind::Gtin13),
+    ///     Err(GtinError::WrongKind {
+    ///         expected: GtinKind::Gtin13,
+    ///         actual_len: 12
+    ///     })
+    /// );
+    /// ```
+    pub fn parse_exact(code: &str, kind: GtinKind) -> Result<Gtin, GtinError> {
+        let code = code.trim();
+        if !code.is_ascii() {
+            return Err(GtinError::NonAsciiString { kind: Some(kind) });
+        }
+        if code.len() != kind.length() {
+            return Err(GtinError::WrongKind {
+                expected: kind,
+                actual_len: code.len(),
+            });
+        }
+
+        match kind {
+            GtinKind::Gtin8 => Ok(Gtin::from_valid(GtinKind::Gtin8, gtin8::fix(code)?)),
+            GtinKind::Gtin12 => Ok(Gtin::from_valid(GtinKind::Gtin12, gtin12::fix(code)?)),
+            GtinKind::Gtin13 => Ok(Gtin::from_valid(GtinKind::Gtin13, gtin13::fix(code)?)),
+            GtinKind::Gtin14 => Ok(Gtin::from_valid(GtinKind::Gtin14, gtin14::fix(code)?)),
+        }
+    }
+
+    /// Return the kind (width) of the code.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::{Gtin, GtinKind};
+    ///
+    /// let codes = ["14567810", "897854613315", "1498279802125", "14567815983469"];
+    /// let kinds: Vec<GtinKind> = codes
+    ///     .iter()
+    ///     .map(|code| Gtin::parse(code).unwrap().kind())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     kinds,
+    ///     [GtinKind::Gtin8, GtinKind::Gtin12, GtinKind::Gtin13, GtinKind::Gtin14]
+    /// );
+    /// assert_eq!(kinds[2].to_string(), "GTIN-13");
+    /// ```
+    pub fn kind(&self) -> GtinKind {
+        match *self {
+            Gtin::Gtin8(_) => GtinKind::Gtin8,
+            Gtin::Gtin12(_) => GtinKind::Gtin12,
+            Gtin::Gtin13(_) => GtinKind::Gtin13,
+            Gtin::Gtin14(_) => GtinKind::Gtin14,
+        }
+    }
+
+    /// Return the number of digits in the code.
+    pub fn length(&self) -> usize {
+        self.kind().length()
+    }
+
+    /// Return the digit string of the code.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Gtin::Gtin8(ref code) => code.as_str(),
+            Gtin::Gtin12(ref code) => code.as_str(),
+            Gtin::Gtin13(ref code) => code.as_str(),
+            Gtin::Gtin14(ref code) => code.as_str(),
+        }
+    }
+
+    // Wrap a code of the given width that is already known to be valid,
+    // such as the output of the width's `fix` function.
+    fn from_valid(kind: GtinKind, code: String) -> Gtin {
+        match kind {
+            GtinKind::Gtin8 => Gtin::Gtin8(gtin8::Gtin8::from_valid(code)),
+            GtinKind::Gtin12 => Gtin::Gtin12(gtin12::Gtin12::from_valid(code)),
+            GtinKind::Gtin13 => Gtin::Gtin13(gtin13::Gtin13::from_valid(code)),
+            GtinKind::Gtin14 => Gtin::Gtin14(gtin14::Gtin14::from_valid(code)),
+        }
+    }
+
+    /// Return the code in GTIN-14 form, whatever its width.
+    ///
+    /// The stored code is already valid, so it only needs padding with
+    /// leading zeros, which does not change the check digit. This is the
+    /// same form produced by
+    /// [`normalize_to_gtin14`](fn.normalize_to_gtin14.html).
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::Gtin;
+    ///
+    /// let gtin = Gtin::parse("036000291452").unwrap();
+    /// assert_eq!(gtin.to_gtin14(), "00036000291452");
+    /// ```
+    pub fn to_gtin14(&self) -> String {
+        utils::zero_pad(self.as_str(), 14).into_owned()
+    }
+
+    /// Return a key identifying the product the code refers to,
+    /// regardless of its width: the code as a GTIN-14, as returned by
+    /// [`to_gtin14`](#method.to_gtin14).
+    ///
+    /// Equality of `Gtin` values is strict, comparing both the kind and
+    /// the digits, while equal canonical keys mean the same product. Use
+    /// the key in place of the `Gtin` in a `HashMap` or `HashSet` to
+    /// merge the widths of the same code.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::Gtin;
+    ///
+    /// let upc = Gtin::parse("036000291452").unwrap();
+    /// let ean = Gtin::parse("0036000291452").unwrap();
+    /// assert_ne!(upc, ean);
+    /// assert_eq!(upc.canonical_key(), ean.canonical_key());
+    /// ```
+    pub fn canonical_key(&self) -> String {
+        self.to_gtin14()
+    }
+
+    /// Check whether two codes refer to the same product, regardless of
+    /// width, by comparing their [`canonical_key`](#method.canonical_key)s.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::Gtin;
+    ///
+    /// let upc = Gtin::parse("036000291452").unwrap();
+    /// let ean = Gtin::parse("0036000291452").unwrap();
+    /// assert!(upc.same_product(&ean));
+    /// assert!(!upc.same_product(&Gtin::parse("4006381333931").unwrap()));
+    /// ```
+    pub fn same_product(&self, other: &Gtin) -> bool {
+        self.canonical_key() == other.canonical_key()
+    }
+
+    /// Return the same product as a code of the narrowest width that can
+    /// represent it, judged by its leading zeros as by
+    /// [`gtin14::shortest_kind`](gtin14/fn.shortest_kind.html).
+    ///
+    /// A GTIN-14 padded from a UPC-A collapses back to a `Gtin12`, for
+    /// example. As with `shortest_kind`, this is structural: a code with
+    /// enough leading zeros collapses even if it was originally issued at
+    /// a wider width.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::Gtin;
+    ///
+    /// let gtin = Gtin::parse("00036000291452").unwrap();
+    /// assert_eq!(gtin.as_shortest(), Gtin::Gtin12("036000291452".parse().unwrap()));
+    ///
+    /// let gtin = Gtin::parse("4006381333931").unwrap();
+    /// assert_eq!(gtin.as_shortest(), gtin);
+    /// ```
+    pub fn as_shortest(&self) -> Gtin {
+        let gtin14 = self.to_gtin14();
+        let kind = gtin14::narrowest_kind(&gtin14);
+        let code = gtin14[14 - kind.length()..].to_string();
+
+        Gtin::from_valid(kind, code)
+    }
+}
+
+/// Display the digit string of the code, without the kind.
+impl fmt::Display for Gtin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parse a GTIN of any width with [`Gtin::parse`](#method.parse), so
+/// that the output of `Display` can be read back.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::Gtin;
+///
+/// for code in &["14567810", "897854613315", "1498279802125", "14567815983469"] {
+///     let gtin: Gtin = code.parse().unwrap();
+///     assert_eq!(gtin.to_string(), *code);
+///     assert_eq!(gtin.to_string().parse::<Gtin>(), Ok(gtin));
+/// }
+/// ```
+impl FromStr for Gtin {
+    type Err = GtinError;
+
+    fn from_str(code: &str) -> Result<Gtin, GtinError> {
+        Gtin::parse(code)
+    }
+}
+
+/// Fix a code whose width is not known, settling on the most likely
+/// intended width, and return the fixed code along with that width.
+///
+/// Whitespace is stripped from the left and right sides. The widths are
+/// then tried in this order:
+///
+/// 1. The width equal to the trimmed length, if there is one; the code
+///    is checked as it is.
+/// 2. Otherwise, the next larger width, zero-padding the code into it.
+///    An 11-digit code is tried as a GTIN-12, for example, and a code of
+///    fewer than 8 digits as a GTIN-8.
+///
+/// Zero-padding never changes whether the check digit is correct, so a
+/// code that fails at the width matching its length cannot succeed at a
+/// larger width, and its error is returned. Empty input is rejected
+/// with `TooShort`, and input longer than 14 digits with `TooLong`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{fix_best_effort, Gtin, GtinKind};
+///
+/// assert_eq!(
+///     fix_best_effort("036000291452"),
+///     Ok((Gtin::Gtin12("036000291452".parse().unwrap()), GtinKind::Gtin12))
+/// );
+/// assert_eq!(
+///     fix_best_effort("36000291452"),
+///     Ok((Gtin::Gtin12("036000291452".parse().unwrap()), GtinKind::Gtin12))
+/// );
+/// assert!(fix_best_effort("36000291453").is_err());
+/// ```
+pub fn fix_best_effort(code: &str) -> Result<(Gtin, GtinKind), GtinError> {
+    let trimmed = code.trim();
+    if !trimmed.is_ascii() {
+        return Err(GtinError::NonAsciiString { kind: None });
+    }
+
+    let gtin = match trimmed.len() {
+        0 => return Err(GtinError::TooShort { kind: None }),
+        1..=8 => Gtin::from_valid(GtinKind::Gtin8, gtin8::fix(trimmed)?),
+        9..=12 => Gtin::from_valid(GtinKind::Gtin12, gtin12::fix(trimmed)?),
+        13 => Gtin::from_valid(GtinKind::Gtin13, gtin13::fix(trimmed)?),
+        14 => Gtin::from_valid(GtinKind::Gtin14, gtin14::fix(trimmed)?),
+        len => {
+            return Err(GtinError::TooLong {
+                kind: None,
+                trimmed_len: len,
+            })
+        }
+    };
+    let kind = gtin.kind();
+
+    Ok((gtin, kind))
+}
+
+/// Count the leading `'0'` digits of a valid GTIN of any width.
+///
+/// The code is parsed as by [`Gtin::parse`](enum.Gtin.html#method.parse)
+/// first, so whitespace is stripped from both sides and an invalid or
+/// non-numeric code gives an error. Counted across a column that was
+/// zero-padded to a single width, such as GTIN-14, this shows how many
+/// codes were originally of a shorter width. As with
+/// [`gtin14::shortest_kind`](gtin14/fn.shortest_kind.html), the count is
+/// structural: a company prefix may start with zeros of its own.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::leading_zeros;
+///
+/// assert_eq!(leading_zeros("00000014567810"), Ok(6));
+/// assert_eq!(leading_zeros("036000291452"), Ok(1));
+/// assert_eq!(leading_zeros("4006381333931"), Ok(0));
+/// assert!(leading_zeros("0003600029145A").is_err());
+/// ```
+pub fn leading_zeros(code: &str) -> Result<usize, GtinError> {
+    let gtin = Gtin::parse(code)?;
+
+    Ok(gtin.as_str().bytes().take_while(|&b| b == b'0').count())
+}
+
+/// The unvalidated form of a `Gtin`, with the same serialized shape.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(tag = "kind", content = "code")]
+enum GtinRepr {
+    Gtin8(CodeRepr),
+    Gtin12(CodeRepr),
+    Gtin13(CodeRepr),
+    Gtin14(CodeRepr),
+}
+
+/// The code of a `GtinRepr`, written either as a string or as an integer
+/// that has lost its leading zeros.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CodeRepr {
+    Text(String),
+    Number(u64),
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Gtin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Gtin, D::Error> {
+        let (kind, code) = match GtinRepr::deserialize(deserializer)? {
+            GtinRepr::Gtin8(code) => (GtinKind::Gtin8, code),
+            GtinRepr::Gtin12(code) => (GtinKind::Gtin12, code),
+            GtinRepr::Gtin13(code) => (GtinKind::Gtin13, code),
+            GtinRepr::Gtin14(code) => (GtinKind::Gtin14, code),
+        };
+        let code = match code {
+            CodeRepr::Text(code) => code,
+            CodeRepr::Number(number) => {
+                let digits = number.to_string();
+                if digits.len() > kind.length() {
+                    return Err(de::Error::custom(format_args!(
+                        "{} has too many digits for a {} code",
+                        number, kind
+                    )));
+                }
+                utils::zero_pad(&digits, kind.length()).into_owned()
+            }
+        };
+
+        let valid = match kind {
+            GtinKind::Gtin8 => gtin8::check(&code),
+            GtinKind::Gtin12 => gtin12::check(&code),
+            GtinKind::Gtin13 => gtin13::check(&code),
+            GtinKind::Gtin14 => gtin14::check(&code),
+        };
+        if valid {
+            Ok(Gtin::from_valid(kind, code))
+        } else {
+            Err(de::Error::custom(format_args!(
+                "invalid {} code: {:?}",
+                kind, code
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fix_best_effort;
+    use super::leading_zeros;
+    use super::Gtin;
+    use super::GtinError;
+    use super::GtinKind;
+    use gtin12;
+    use gtin13;
+    use gtin14;
+    use gtin8;
+    use normalize::normalize_to_gtin14;
+    use std::collections::{BTreeMap, HashSet};
+
+    #[test]
+    fn variants_hold_validated_codes() {
+        let code: Result<gtin13::Gtin13, GtinError> = "4006381333932".parse();
+        assert_eq!(
+            code,
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 1,
+                found: 2
+            })
+        );
+        let gtin = Gtin::Gtin13("4006381333931".parse().unwrap());
+        assert_eq!(gtin.as_str(), "4006381333931");
+        assert_eq!(gtin.as_shortest(), gtin);
+    }
+
+    #[test]
+    fn parse_each_width() {
+        assert_eq!(
+            Gtin::parse("14567810"),
+            Ok(Gtin::Gtin8("14567810".parse().unwrap()))
+        );
+        assert_eq!(
+            Gtin::parse("897854613315"),
+            Ok(Gtin::Gtin12("897854613315".parse().unwrap()))
+        );
+        assert_eq!(
+            Gtin::parse("1498279802125"),
+            Ok(Gtin::Gtin13("1498279802125".parse().unwrap()))
+        );
+        assert_eq!(
+            Gtin::parse(" 14567815983469\n"),
+            Ok(Gtin::Gtin14("14567815983469".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn fix_best_effort_matching_width() {
+        assert_eq!(
+            fix_best_effort("14567810"),
+            Ok((Gtin::Gtin8("14567810".parse().unwrap()), GtinKind::Gtin8))
+        );
+        assert_eq!(
+            fix_best_effort(" 036000291452\n"),
+            Ok((
+                Gtin::Gtin12("036000291452".parse().unwrap()),
+                GtinKind::Gtin12
+            ))
+        );
+        assert_eq!(
+            fix_best_effort("4006381333931"),
+            Ok((
+                Gtin::Gtin13("4006381333931".parse().unwrap()),
+                GtinKind::Gtin13
+            ))
+        );
+        assert_eq!(
+            fix_best_effort("14567815983469"),
+            Ok((
+                Gtin::Gtin14("14567815983469".parse().unwrap()),
+                GtinKind::Gtin14
+            ))
+        );
+    }
+
+    #[test]
+    fn fix_best_effort_pads_into_next_width() {
+        assert_eq!(
+            fix_best_effort("36000291452"),
+            Ok((
+                Gtin::Gtin12("036000291452".parse().unwrap()),
+                GtinKind::Gtin12
+            ))
+        );
+        assert_eq!(
+            fix_best_effort("1234565"),
+            Ok((Gtin::Gtin8("01234565".parse().unwrap()), GtinKind::Gtin8))
+        );
+        assert_eq!(
+            fix_best_effort("123456784"),
+            Ok((
+                Gtin::Gtin12("000123456784".parse().unwrap()),
+                GtinKind::Gtin12
+            ))
+        );
+    }
+
+    #[test]
+    fn fix_best_effort_errors() {
+        assert_eq!(
+            fix_best_effort("36000291453"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin12),
+                expected: 2,
+                found: 3,
+            })
+        );
+        assert_eq!(
+            fix_best_effort("4006381333932"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 1,
+                found: 2,
+            })
+        );
+        assert_eq!(
+            fix_best_effort(" "),
+            Err(GtinError::TooShort { kind: None })
+        );
+        assert_eq!(
+            fix_best_effort("123456789012345"),
+            Err(GtinError::TooLong {
+                kind: None,
+                trimmed_len: 15
+            })
+        );
+        assert_eq!(
+            fix_best_effort("❤"),
+            Err(GtinError::NonAsciiString { kind: None })
+        );
+        assert_eq!(
+            fix_best_effort("3600029145A"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin12)
+            })
+        );
+    }
+
+    #[test]
+    fn parse_exact_each_width() {
+        let codes = [
+            (GtinKind::Gtin8, "14567810"),
+            (GtinKind::Gtin12, "036000291452"),
+            (GtinKind::Gtin13, "4006381333931"),
+            (GtinKind::Gtin14, " 14567815983469\n"),
+        ];
+        for &(kind, code) in &codes {
+            let gtin = Gtin::parse_exact(code, kind).unwrap();
+            assert_eq!(gtin.kind(), kind);
+            assert_eq!(gtin, Gtin::parse(code).unwrap());
+        }
+    }
+
+    #[test]
+    fn parse_exact_upca_as_gtin13() {
+        let err = Gtin::parse_exact("036000291452", GtinKind::Gtin13).unwrap_err();
+        assert_eq!(
+            err,
+            GtinError::WrongKind {
+                expected: GtinKind::Gtin13,
+                actual_len: 12
+            }
+        );
+        assert_eq!(err.kind(), Some(GtinKind::Gtin13));
+    }
+
+    #[test]
+    fn parse_exact_errors() {
+        assert_eq!(
+            Gtin::parse_exact("4006381333932", GtinKind::Gtin13),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Gtin::parse_exact("400638133393A", GtinKind::Gtin13),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin13)
+            })
+        );
+        assert_eq!(
+            Gtin::parse_exact("❤", GtinKind::Gtin8),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin8)
+            })
+        );
+        assert_eq!(
+            Gtin::parse_exact("", GtinKind::Gtin14),
+            Err(GtinError::WrongKind {
+                expected: GtinKind::Gtin14,
+                actual_len: 0
+            })
+        );
+    }
+
+    #[test]
+    fn leading_zeros_each_width() {
+        assert_eq!(leading_zeros("00000014567810"), Ok(6));
+        assert_eq!(leading_zeros("00036000291452"), Ok(3));
+        assert_eq!(leading_zeros(" 036000291452\n"), Ok(1));
+        assert_eq!(leading_zeros("04006381333931"), Ok(1));
+        assert_eq!(leading_zeros("14567810"), Ok(0));
+        assert_eq!(leading_zeros("14567815983469"), Ok(0));
+    }
+
+    #[test]
+    fn leading_zeros_invalid() {
+        assert_eq!(
+            leading_zeros("0003600029145A"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14)
+            })
+        );
+        assert_eq!(
+            leading_zeros("00036000291453"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin14),
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(leading_zeros(""), Err(GtinError::TooShort { kind: None }));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Gtin::parse(""), Err(GtinError::TooShort { kind: None }));
+        assert_eq!(
+            Gtin::parse("1234567"),
+            Err(GtinError::TooShort { kind: None })
+        );
+        assert_eq!(Gtin::parse("123456789"), Err(GtinError::InvalidLength));
+        assert_eq!(
+            Gtin::parse("123456789012345"),
+            Err(GtinError::TooLong {
+                kind: None,
+                trimmed_len: 15
+            })
+        );
+        assert_eq!(
+            Gtin::parse("❤"),
+            Err(GtinError::NonAsciiString { kind: None })
+        );
+        assert_eq!(
+            Gtin::parse("❤❤❤❤"),
+            Err(GtinError::NonAsciiString { kind: None })
+        );
+        assert_eq!(
+            Gtin::parse("14567811"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin8),
+                expected: 0,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn gtin_error_from_fix_error() {
+        assert_eq!(
+            GtinError::from(gtin8::FixError::TooShort),
+            GtinError::TooShort {
+                kind: Some(GtinKind::Gtin8)
+            }
+        );
+        assert_eq!(
+            GtinError::from(gtin12::FixError::TooLong { trimmed_len: 13 }),
+            GtinError::TooLong {
+                kind: Some(GtinKind::Gtin12),
+                trimmed_len: 13
+            }
+        );
+        assert_eq!(
+            GtinError::from(gtin13::FixError::Ambiguous).kind(),
+            Some(GtinKind::Gtin13)
+        );
+        assert_eq!(
+            GtinError::from(gtin14::FixError::NotRepresentable).kind(),
+            Some(GtinKind::Gtin14)
+        );
+        assert_eq!(GtinError::InvalidLength.kind(), None);
+    }
+
+    #[test]
+    fn kind_and_length() {
+        for code in &[
+            "14567810",
+            "897854613315",
+            "1498279802125",
+            "14567815983469",
+        ] {
+            let gtin = Gtin::parse(code).unwrap();
+            assert_eq!(gtin.length(), code.len());
+            assert_eq!(gtin.kind().length(), code.len());
+        }
+        assert_eq!(Gtin::parse("14567810").unwrap().kind(), GtinKind::Gtin8);
+    }
+
+    #[test]
+    fn kind_display() {
+        assert_eq!(GtinKind::Gtin8.to_string(), "GTIN-8");
+        assert_eq!(GtinKind::Gtin12.to_string(), "GTIN-12");
+        assert_eq!(GtinKind::Gtin13.to_string(), "GTIN-13");
+        assert_eq!(GtinKind::Gtin14.to_string(), "GTIN-14");
+    }
+
+    #[test]
+    fn detect_by_length() {
+        assert_eq!(GtinKind::detect("14567811"), Some(GtinKind::Gtin8));
+        assert_eq!(GtinKind::detect("ABCDEFGHIJKL"), Some(GtinKind::Gtin12));
+        assert_eq!(
+            GtinKind::detect("\t1498279802125\n"),
+            Some(GtinKind::Gtin13)
+        );
+        assert_eq!(GtinKind::detect("14567815983469"), Some(GtinKind::Gtin14));
+        assert_eq!(GtinKind::detect(""), None);
+        assert_eq!(GtinKind::detect("1234567"), None);
+        assert_eq!(GtinKind::detect("123456789"), None);
+        assert_eq!(GtinKind::detect("12345678901"), None);
+        assert_eq!(GtinKind::detect("123456789012345"), None);
+    }
+
+    #[test]
+    fn detect_valid_requires_check_digit() {
+        assert_eq!(GtinKind::detect_valid("14567810"), Some(GtinKind::Gtin8));
+        assert_eq!(GtinKind::detect_valid("14567811"), None);
+        assert_eq!(
+            GtinKind::detect_valid(" 897854613315 "),
+            Some(GtinKind::Gtin12)
+        );
+        assert_eq!(GtinKind::detect_valid("ABCDEFGHIJKL"), None);
+        assert_eq!(GtinKind::detect_valid("123456789"), None);
+        assert_eq!(
+            GtinKind::detect_valid("14567815983469"),
+            Some(GtinKind::Gtin14)
+        );
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let gtin: Gtin = " 036000291452".parse().unwrap();
+        assert_eq!(gtin, Gtin::Gtin12("036000291452".parse().unwrap()));
+        assert_eq!(format!("{}", gtin), "036000291452");
+        assert_eq!("123456789".parse::<Gtin>(), Err(GtinError::InvalidLength));
+    }
+
+    #[test]
+    fn as_str() {
+        assert_eq!(Gtin::parse("14567810").unwrap().as_str(), "14567810");
+    }
+
+    #[test]
+    fn to_gtin14_each_width() {
+        let codes = [
+            ("14567810", "00000014567810"),
+            ("897854613315", "00897854613315"),
+            ("1498279802125", "01498279802125"),
+            ("14567815983469", "14567815983469"),
+        ];
+        for &(code, expected) in &codes {
+            let gtin14 = Gtin::parse(code).unwrap().to_gtin14();
+            assert_eq!(gtin14, expected);
+            assert_eq!(normalize_to_gtin14(code), Ok(gtin14));
+        }
+    }
+
+    #[test]
+    fn canonical_key_across_widths() {
+        let upc = Gtin::parse("036000291452").unwrap();
+        let ean = Gtin::parse("0036000291452").unwrap();
+        let gtin14 = Gtin::parse("00036000291452").unwrap();
+        assert_eq!(upc.canonical_key(), "00036000291452");
+        assert_eq!(upc.canonical_key(), ean.canonical_key());
+        assert_eq!(upc.canonical_key(), gtin14.canonical_key());
+    }
+
+    #[test]
+    fn same_product_across_widths() {
+        let upc = Gtin::parse("036000291452").unwrap();
+        let ean = Gtin::parse("0036000291452").unwrap();
+        let gtin8 = Gtin::parse("14567810").unwrap();
+        assert!(upc.same_product(&ean));
+        assert!(ean.same_product(&upc));
+        assert!(upc.same_product(&upc));
+        assert!(gtin8.same_product(&Gtin::parse("00000014567810").unwrap()));
+        assert!(!gtin8.same_product(&upc));
+        assert!(!upc.same_product(&Gtin::parse("10036000291459").unwrap()));
+    }
+
+    #[test]
+    fn btreemap_iterates_in_order() {
+        let mut map = BTreeMap::new();
+        for code in &[
+            "14567815983469",
+            "4006381333931",
+            "897854613315",
+            "14567810",
+            "0036000291452",
+            "036000291452",
+            "00000014567810",
+        ] {
+            map.insert(Gtin::parse(code).unwrap(), code.len());
+        }
+        let keys: Vec<&Gtin> = map.keys().collect();
+        assert_eq!(
+            keys,
+            vec![
+                &Gtin::Gtin8("14567810".parse().unwrap()),
+                &Gtin::Gtin12("036000291452".parse().unwrap()),
+                &Gtin::Gtin12("897854613315".parse().unwrap()),
+                &Gtin::Gtin13("0036000291452".parse().unwrap()),
+                &Gtin::Gtin13("4006381333931".parse().unwrap()),
+                &Gtin::Gtin14("00000014567810".parse().unwrap()),
+                &Gtin::Gtin14("14567815983469".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn as_shortest_collapses() {
+        let upc = Gtin::parse("036000291452").unwrap();
+        let gtin14 = Gtin::parse(&upc.to_gtin14()).unwrap();
+        assert_eq!(gtin14, Gtin::Gtin14("00036000291452".parse().unwrap()));
+        assert_eq!(gtin14.as_shortest(), upc);
+
+        let ean = Gtin::parse("0036000291452").unwrap();
+        assert_eq!(ean.as_shortest(), upc);
+
+        let gtin8 = Gtin::parse("00000014567810").unwrap();
+        assert_eq!(
+            gtin8.as_shortest(),
+            Gtin::Gtin8("14567810".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn as_shortest_keeps_narrowest() {
+        for code in &[
+            "14567810",
+            "897854613315",
+            "4006381333931",
+            "14567815983469",
+        ] {
+            let gtin = Gtin::parse(code).unwrap();
+            assert_eq!(gtin.as_shortest(), gtin);
+        }
+        assert_eq!(
+            Gtin::parse("04006381333931").unwrap().as_shortest(),
+            Gtin::Gtin13("4006381333931".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn equality_requires_same_kind() {
+        let upc = Gtin::parse("036000291452").unwrap();
+        let ean = Gtin::parse("0036000291452").unwrap();
+        assert_ne!(upc, ean);
+        assert_eq!(upc, Gtin::Gtin12("036000291452".parse().unwrap()));
+    }
+
+    #[test]
+    fn hash_set_element() {
+        let mut set = HashSet::new();
+        assert!(set.insert(Gtin::parse("036000291452").unwrap()));
+        assert!(set.insert(Gtin::parse("0036000291452").unwrap()));
+        assert!(!set.insert(Gtin::parse(" 036000291452").unwrap()));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Gtin::Gtin13("0036000291452".parse().unwrap())));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let gtins: Vec<Gtin> = [
+            "14567810",
+            "897854613315",
+            "1498279802125",
+            "14567815983469",
+        ]
+        .iter()
+        .map(|code| Gtin::parse(code).unwrap())
+        .collect();
+        let json = ::serde_json::to_string(&gtins).unwrap();
+        assert!(json.starts_with(r#"[{"kind":"Gtin8","code":"14567810"},"#));
+        let decoded: Vec<Gtin> = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, gtins);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_accepts_integers() {
+        let decoded: Gtin =
+            ::serde_json::from_str(r#"{"kind":"Gtin12","code":87248795257}"#).unwrap();
+        assert_eq!(decoded, Gtin::Gtin12("087248795257".parse().unwrap()));
+
+        let decoded: Gtin =
+            ::serde_json::from_str(r#"{"kind":"Gtin12","code":"087248795257"}"#).unwrap();
+        assert_eq!(decoded, Gtin::Gtin12("087248795257".parse().unwrap()));
+
+        let decoded: Gtin =
+            ::serde_json::from_str(r#"{"kind":"Gtin14","code":14567815983469}"#).unwrap();
+        assert_eq!(decoded, Gtin::Gtin14("14567815983469".parse().unwrap()));
+
+        let decoded: Gtin = ::serde_json::from_str(r#"{"kind":"Gtin8","code":0}"#).unwrap();
+        assert_eq!(decoded, Gtin::Gtin8("00000000".parse().unwrap()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_invalid_integers() {
+        let invalid = [
+            // Too many digits for the kind, although valid as a GTIN-13
+            r#"{"kind":"Gtin12","code":1498279802125}"#,
+            r#"{"kind":"Gtin14","code":100000000000000000}"#,
+            // Bad check digit
+            r#"{"kind":"Gtin12","code":87248795258}"#,
+            r#"{"kind":"Gtin12","code":-87248795257}"#,
+            r#"{"kind":"Gtin12","code":87248795257.0}"#,
+        ];
+        for json in &invalid {
+            assert!(::serde_json::from_str::<Gtin>(json).is_err(), "{}", json);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_invalid() {
+        let invalid = [
+            r#"{"kind":"Gtin8","code":"14567811"}"#,
+            r#"{"kind":"Gtin13","code":"897854613315"}"#,
+            r#"{"kind":"Gtin12","code":" 897854613315"}"#,
+            r#"{"kind":"Gtin15","code":"897854613315"}"#,
+            r#"{"code":"897854613315"}"#,
+        ];
+        for json in &invalid {
+            assert!(::serde_json::from_str::<Gtin>(json).is_err(), "{}", json);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_doesnt_crash(ref s in ".*") {
+            let _ = Gtin::parse(s);
+        }
+
+        #[test]
+        fn as_shortest_is_same_product(ref s in "[0-9]{8,14}") {
+            if let Ok(gtin) = Gtin::parse(s) {
+                let shortest = gtin.as_shortest();
+                assert!(shortest.same_product(&gtin));
+                assert!(shortest.length() <= gtin.length());
+                assert_eq!(shortest.as_shortest(), shortest);
+            }
+        }
+
+        #[test]
+        fn fix_best_effort_agrees_with_parse(ref s in "[0-9]{8,14}") {
+            if let Ok(gtin) = Gtin::parse(s) {
+                let kind = gtin.kind();
+                assert_eq!(fix_best_effort(s), Ok((gtin, kind)));
+            }
+        }
+
+        #[test]
+        fn display_round_trips(ref s in "[0-9]{8,14}") {
+            if let Ok(gtin) = Gtin::parse(s) {
+                assert_eq!(gtin.to_string().parse::<Gtin>(), Ok(gtin));
+            }
+        }
+
+        #[test]
+        fn same_product_is_symmetric(ref a in "[0-9]{8,14}", ref b in "[0-9]{8,14}") {
+            if let (Ok(a), Ok(b)) = (Gtin::parse(a), Gtin::parse(b)) {
+                assert_eq!(a.same_product(&b), b.same_product(&a));
+                assert!(a.same_product(&Gtin::parse(&a.canonical_key()).unwrap()));
+            }
+        }
+
+        #[test]
+        fn to_gtin14_passes_check(ref s in "[0-9]{8,14}") {
+            if let Ok(gtin) = Gtin::parse(s) {
+                assert!(gtin14::check(gtin.to_gtin14()));
+            }
+        }
+
+        #[test]
+        fn detect_valid_implies_detect(ref s in ".*") {
+            if let Some(kind) = GtinKind::detect_valid(s) {
+                assert_eq!(GtinKind::detect(s), Some(kind));
+            }
+        }
+    }
+}
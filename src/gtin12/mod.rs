@@ -1,21 +1,117 @@
 //! Performs validation and correction of GTIN-12 and UPC-A codes.
 
+use std::str;
+
+use context::InputError;
 use utils;
 
 /// Errors that make GTIN-12 correction impossible.
+///
+/// The `FixError` types of all GTIN modules share the same variants, so
+/// this type can be converted from any of them with `From`/`Into`. Each
+/// variant maps to the variant of the same name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FixError {
     /// The provided string contains non-ASCII characters.
     NonAsciiString,
+    /// The provided string contains characters other than ASCII digits.
+    NonNumericString,
     /// The provided code was too long to be valid.
-    TooLong,
+    TooLong {
+        /// The length of the code in bytes, after any whitespace
+        /// trimming done by the function returning the error. A length
+        /// within the expected width means that the input was rejected
+        /// only because of its surrounding whitespace.
+        trimmed_len: usize,
+    },
+    /// The provided code was too short to be valid.
+    TooShort,
     /// The calculated check-digit did not match the code's check-digit.
-    CheckDigitIncorrect,
+    CheckDigitIncorrect {
+        /// The check digit computed from the other digits of the code.
+        expected: u8,
+        /// The check digit the code ends with.
+        found: u8,
+    },
+    /// More than one correction of the code is possible, so none was
+    /// chosen.
+    Ambiguous,
+    /// The code is valid, but cannot be used for or converted into the
+    /// requested form.
+    NotRepresentable,
+}
+
+impl_fix_error_from!(gtin8, gtin13, gtin14);
+
+/// The longest input, in bytes, that [`fix`](fn.fix.html) will examine.
+///
+/// This is the 12-digit code plus an allowance for surrounding
+/// whitespace. Longer input is rejected with `TooLong` before it is
+/// copied, which bounds the work done on pathological input. The error
+/// still reports the trimmed length, so input that was only too long
+/// because of its whitespace can be told apart.
+pub const MAX_INPUT_LEN: usize = 12 + 32;
+
+gtin_newtype! {
+    /// A validated GTIN-12 (UPC-A) code.
+    ///
+    /// Values can only be constructed from input that passes
+    /// [`check`](fn.check.html), so the wrapped string is always a valid
+    /// code. Construct them with `TryFrom`, from either a `&str` or the raw
+    /// bytes of the code.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::gtin12::Gtin12;
+    /// use std::convert::TryFrom;
+    ///
+    /// let code = Gtin12::try_from(&b"897854613315"[..]).unwrap();
+    /// assert_eq!(code.as_str(), "897854613315");
+    /// assert!(Gtin12::try_from("897854613316").is_err()); // Bad check digit
+    /// ```
+    pub struct Gtin12(12);
+}
+
+/// The category of a UPC-A code, given by its number system (first)
+/// digit, as returned by
+/// [`number_system_kind`](fn.number_system_kind.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberSystem {
+    /// Number systems 0, 1, 6, 7 and 8: regular products.
+    Regular,
+    /// Number system 2: random-weight items such as meat and produce,
+    /// priced at the store.
+    VariableWeight,
+    /// Number system 3: drugs and health products, identified by their
+    /// National Drug Code or NHRIC.
+    Drug,
+    /// Number system 4: codes for use within a store, such as loyalty
+    /// cards or in-store items, without a restriction on format.
+    InStore,
+    /// Number systems 5 and 9: coupons.
+    Coupon,
+}
+
+/// The fields of a UPC-A coupon code, as returned by
+/// [`coupon_fields`](fn.coupon_fields.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CouponFields {
+    /// The 5-digit manufacturer number, taken from the company prefix of
+    /// the manufacturer's product codes.
+    pub company_prefix: String,
+    /// The 3-digit family code, identifying the products the coupon can
+    /// be redeemed against.
+    pub family_code: String,
+    /// The 2-digit value code, identifying the discount.
+    pub value_code: String,
 }
 
 /// Check that a UPC-A code is valid by confirming that it is made of
 /// exactly 12 digits and that the check-digit is correct.
 ///
+/// The code may be any string type, such as `&str`, `String` or
+/// `Cow<str>`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin12;
@@ -23,23 +119,94 @@ pub enum FixError {
 /// assert_eq!(gtin12::check("897854613315"), true);  // Valid GTIN-12
 /// assert_eq!(gtin12::check("89785461331"), false);  // Too short
 /// assert_eq!(gtin12::check("897854613318"), false); // Bad check digit
+/// assert_eq!(gtin12::check(String::from("897854613315")), true);
 /// ```
-pub fn check(code: &str) -> bool {
-    if code.len() != 12 {
-        return false;
-    }
-    if !utils::is_ascii_numeric(code) {
-        return false;
+#[must_use = "the result of the check tells whether the code is valid"]
+pub fn check(code: impl AsRef<str>) -> bool {
+    fn inner(code: &str) -> bool {
+        if code.is_empty() {
+            return false;
+        }
+        if code.len() != 12 {
+            return false;
+        }
+        if !utils::is_ascii_numeric(code) {
+            return false;
+        }
+
+        // Calculate and compare check digit
+        let bytes = code.as_bytes();
+        let check = utils::compute_check_digit(bytes);
+        if check != bytes[11] - 48 {
+            return false;
+        }
+
+        true
     }
 
-    // Calculate and compare check digit
-    let bytes = code.as_bytes();
-    let check = utils::compute_check_digit(bytes);
-    if check != bytes[11] - 48 {
-        return false;
+    inner(code.as_ref())
+}
+
+/// Check that a UPC-A code is valid after stripping ASCII whitespace
+/// from the left and right sides.
+///
+/// This is meant for codes read line-by-line from files, which often
+/// keep a trailing `\n` or `\r\n`. Unlike [`fix`](fn.fix.html), no
+/// zero-padding is performed and nothing is allocated. Use the strict
+/// [`check`](fn.check.html) when the input is known to be clean, such as
+/// in performance-sensitive inner loops.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::check("036000291452\n"), false);
+/// assert_eq!(gtin12::check_trimmed("036000291452\n"), true);
+/// assert_eq!(gtin12::check_trimmed(" 036000291452\r\n"), true);
+/// assert_eq!(gtin12::check_trimmed("36000291452\n"), false); // Too short
+/// ```
+pub fn check_trimmed(code: &str) -> bool {
+    check(code.trim_matches(|c: char| c.is_ascii_whitespace()))
+}
+
+/// Check a UPC-A code whose check digit may be written after a
+/// separator, such as `03600029145-2`.
+///
+/// A single occurrence of `sep` immediately before the final digit is
+/// removed before validating with [`check`](fn.check.html). Only that
+/// one position is tolerated: a separator anywhere else, or more than
+/// one separator, makes the code invalid. Codes without the separator
+/// are checked as they are.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::check_with_separator("03600029145-2", '-'), true);
+/// assert_eq!(gtin12::check_with_separator("036000291452", '-'), true);
+/// assert_eq!(gtin12::check_with_separator("0360002914-52", '-'), false);
+/// assert_eq!(gtin12::check_with_separator("03600029145--2", '-'), false);
+/// ```
+pub fn check_with_separator(code: &str, sep: char) -> bool {
+    let mut chars = code.char_indices().rev();
+    if let (Some((last, _)), Some((at, c))) = (chars.next(), chars.next()) {
+        if c == sep {
+            let (body, check_digit) = (&code[..at], &code[last..]);
+            if body.len() != 11 || check_digit.len() != 1 {
+                return false;
+            }
+
+            let mut joined = [0u8; 12];
+            joined[..11].copy_from_slice(body.as_bytes());
+            joined[11] = check_digit.as_bytes()[0];
+            return match str::from_utf8(&joined) {
+                Ok(joined) => check(joined),
+                Err(_) => false,
+            };
+        }
     }
 
-    true
+    check(code)
 }
 
 /// Attempt to fix invalid UPC codes by stripping whitespace from the
@@ -50,6 +217,10 @@ pub fn check(code: &str) -> bool {
 /// entry and software that treats UPCs as integers rather than strings,
 /// thus truncating leading zeros.
 ///
+/// As with [`check`](fn.check.html), the code may be any string type.
+/// Input longer than [`MAX_INPUT_LEN`](constant.MAX_INPUT_LEN.html)
+/// bytes is rejected with `TooLong`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin12;
@@ -72,32 +243,395 @@ pub fn check(code: &str) -> bool {
 /// let result = gtin12::fix("123412341234123"); // UPC too long
 /// assert!(result.is_err());
 /// ```
-pub fn fix(code: &str) -> Result<String, FixError> {
-    let mut fixed = code.trim().to_string();
+#[must_use = "the fixed code is returned rather than modified in place"]
+pub fn fix(code: impl AsRef<str>) -> Result<String, FixError> {
+    #[inline]
+    fn inner(code: &str) -> Result<String, FixError> {
+        if code.len() > MAX_INPUT_LEN {
+            return Err(FixError::TooLong {
+                trimmed_len: code.trim().len(),
+            });
+        }
+        let trimmed = code.trim();
 
-    if !fixed.is_ascii() {
-        return Err(FixError::NonAsciiString);
+        if !trimmed.is_ascii() {
+            return Err(FixError::NonAsciiString);
+        }
+        if trimmed.len() > 12 {
+            return Err(FixError::TooLong {
+                trimmed_len: trimmed.len(),
+            });
+        }
+        if trimmed.len() < 12 {
+            return fix_padded(trimmed);
+        }
+        if !check(trimmed) {
+            return Err(check_digit_error(trimmed));
+        }
+
+        Ok(trimmed.to_string())
     }
-    if fixed.len() > 12 {
-        return Err(FixError::TooLong);
+
+    inner(code.as_ref())
+}
+
+// Codes that need padding are the uncommon case, so keep this path out
+// of line to leave the full-length path in `fix` small.
+#[cold]
+#[inline(never)]
+fn fix_padded(trimmed: &str) -> Result<String, FixError> {
+    let padded = utils::zero_pad(trimmed, 12).into_owned();
+    if !check(&padded) {
+        return Err(check_digit_error(&padded));
     }
-    fixed = utils::zero_pad(fixed, 12);
-    if !check(&fixed) {
-        return Err(FixError::CheckDigitIncorrect);
+
+    Ok(padded)
+}
+
+/// Fix a GTIN-12 code as [`fix`](fn.fix.html) does, keeping a copy of the
+/// trimmed input in the error.
+///
+/// The copy is only made when `fix` fails, and only with the
+/// `error-input` feature. See [`InputError`](../struct.InputError.html).
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// let err = gtin12::fix_with_input("036000291453 ").unwrap_err();
+/// #[cfg(feature = "error-input")]
+/// assert_eq!(err.input(), Some("036000291453"));
+/// ```
+pub fn fix_with_input(code: &str) -> Result<String, InputError<FixError>> {
+    fix(code).map_err(|error| InputError::new(error, code, MAX_INPUT_LEN))
+}
+
+/// Attempt to correct a GTIN-12 code as [`fix`](fn.fix.html) does, but
+/// refuse to choose when the input has another plausible reading.
+///
+/// Padding with leading zeros never changes the check digit, so the
+/// number of zeros added cannot itself be ambiguous. However, an input
+/// of 8 digits or fewer (after trimming) may also be a zero-suppressed
+/// UPC-E code, which expands to a UPC-A by inserting zeros in the middle
+/// rather than at the start. When both the zero-padded code and the
+/// UPC-E expansion are valid, but different, `Ambiguous` is returned
+/// rather than silently picking the padding.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::fix_unambiguous("87248795257").unwrap(), "087248795257");
+///
+/// // Both 000001234565 and the UPC-E expansion 012345000065 are valid.
+/// assert_eq!(gtin12::fix("1234565").unwrap(), "000001234565");
+/// assert_eq!(
+///     gtin12::fix_unambiguous("1234565"),
+///     Err(gtin12::FixError::Ambiguous)
+/// );
+/// ```
+pub fn fix_unambiguous(code: &str) -> Result<String, FixError> {
+    let fixed = fix(code)?;
+
+    if code.trim().len() <= 8 {
+        if let Some(expanded) = expand_upce(&fixed.as_bytes()[4..]) {
+            if expanded != fixed && check(&expanded) {
+                return Err(FixError::Ambiguous);
+            }
+        }
     }
 
     Ok(fixed)
 }
 
+/// Format a valid UPC-A code with the grouping printed beneath the
+/// symbol.
+///
+/// The number system digit stands alone, followed by the five digits of
+/// the manufacturer number, the five digits of the product number and
+/// the check digit. The groups are separated by single spaces, as in
+/// `0 36000 29145 2`. The code must be exactly 12 digits with a correct
+/// check digit; it is not trimmed or padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::format_grouped("036000291452").unwrap(), "0 36000 29145 2");
+/// assert!(gtin12::format_grouped("036000291453").is_err());
+/// ```
+pub fn format_grouped(code: &str) -> Result<String, FixError> {
+    validate(code)?;
+    Ok(format!(
+        "{} {} {} {}",
+        &code[..1],
+        &code[1..6],
+        &code[6..11],
+        &code[11..]
+    ))
+}
+
+/// Resolve a UPC-A template whose check digit may be left as a
+/// placeholder, as accepted by label-design tools.
+///
+/// When the template ends with `placeholder`, the 11 characters before it
+/// must be digits, and the placeholder is replaced with the correct check
+/// digit. Otherwise the template must already be a valid UPC-A, which is
+/// returned unchanged. The template is not trimmed or padded.
+///
+/// The placeholder must not be a digit, since a final digit could then
+/// never be told apart from a check digit; a digit placeholder is
+/// rejected with `FixError::NonNumericString`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::resolve_template("03600029145?", '?').unwrap(), "036000291452");
+/// assert_eq!(gtin12::resolve_template("036000291452", '?').unwrap(), "036000291452");
+/// assert!(gtin12::resolve_template("036000291453", '?').is_err()); // Bad check digit
+/// ```
+pub fn resolve_template(template: &str, placeholder: char) -> Result<String, FixError> {
+    if placeholder.is_ascii_digit() {
+        return Err(FixError::NonNumericString);
+    }
+
+    let prefix = match template.strip_suffix(placeholder) {
+        Some(prefix) => prefix,
+        None => {
+            validate(template)?;
+            return Ok(template.to_string());
+        }
+    };
+
+    if !prefix.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if prefix.len() > 11 {
+        return Err(FixError::TooLong {
+            trimmed_len: template.len(),
+        });
+    }
+    if prefix.len() < 11 {
+        return Err(FixError::TooShort);
+    }
+    if !utils::is_ascii_numeric(prefix) {
+        return Err(FixError::NonNumericString);
+    }
+
+    let mut code = String::with_capacity(12);
+    code.push_str(prefix);
+    code.push('0');
+    let check = utils::compute_check_digit(code.as_bytes());
+    code.pop();
+    code.push((b'0' + check) as char);
+
+    Ok(code)
+}
+
+/// Return the number system digit of a valid UPC-A code, which is its
+/// first digit.
+///
+/// The code must be exactly 12 digits with a correct check digit; it is
+/// not trimmed or padded. See
+/// [`number_system_kind`](fn.number_system_kind.html) for what the digit
+/// means.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::number_system("036000291452"), Ok(0));
+/// assert_eq!(gtin12::number_system("541000123455"), Ok(5));
+/// assert!(gtin12::number_system("036000291453").is_err()); // Bad check digit
+/// ```
+pub fn number_system(code: &str) -> Result<u8, FixError> {
+    validate(code)?;
+    Ok(code.as_bytes()[0] - b'0')
+}
+
+/// Classify a valid UPC-A code by its number system digit.
+///
+/// The code must be exactly 12 digits with a correct check digit; it is
+/// not trimmed or padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12::{self, NumberSystem};
+///
+/// assert_eq!(gtin12::number_system_kind("036000291452"), Ok(NumberSystem::Regular));
+/// assert_eq!(gtin12::number_system_kind("212345012345"), Ok(NumberSystem::VariableWeight));
+/// assert_eq!(gtin12::number_system_kind("541000123455"), Ok(NumberSystem::Coupon));
+/// ```
+pub fn number_system_kind(code: &str) -> Result<NumberSystem, FixError> {
+    let kind = match number_system(code)? {
+        2 => NumberSystem::VariableWeight,
+        3 => NumberSystem::Drug,
+        4 => NumberSystem::InStore,
+        5 | 9 => NumberSystem::Coupon,
+        _ => NumberSystem::Regular,
+    };
+    Ok(kind)
+}
+
+/// Check whether a code is a valid UPC-A coupon code, which uses number
+/// system `5` or `9`.
+///
+/// This agrees with [`number_system_kind`](fn.number_system_kind.html),
+/// which reports both number systems as `NumberSystem::Coupon`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// assert_eq!(gtin12::is_coupon("541000123455"), true);
+/// assert_eq!(gtin12::is_coupon("941000123453"), true);
+/// assert_eq!(gtin12::is_coupon("036000291452"), false); // Number system 0
+/// assert_eq!(gtin12::is_coupon("541000123456"), false); // Bad check digit
+/// ```
+pub fn is_coupon(code: &str) -> bool {
+    check(code) && (code.starts_with('5') || code.starts_with('9'))
+}
+
+/// Split a valid UPC-A coupon code into its fields.
+///
+/// The classic coupon layout is assumed, with positions counted from 1:
+///
+/// * 1: number system, always `5`
+/// * 2 to 6: manufacturer number (company prefix)
+/// * 7 to 9: family code
+/// * 10 and 11: value code
+/// * 12: check digit
+///
+/// The layout is that of manufacturer coupons in number system `5`.
+/// Number system `9` is also a coupon for [`is_coupon`](fn.is_coupon.html),
+/// but its layout is set by whoever issues the coupons rather than by a
+/// common convention, so its fields cannot be parsed. Returns `None`
+/// unless the code is a valid UPC-A in number system `5`. The code is not
+/// trimmed or padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin12;
+///
+/// let fields = gtin12::coupon_fields("541000123455").unwrap();
+/// assert_eq!(fields.company_prefix, "41000");
+/// assert_eq!(fields.family_code, "123");
+/// assert_eq!(fields.value_code, "45");
+/// ```
+pub fn coupon_fields(code: &str) -> Option<CouponFields> {
+    if !check(code) || !code.starts_with('5') {
+        return None;
+    }
+
+    Some(CouponFields {
+        company_prefix: code[1..6].to_string(),
+        family_code: code[6..9].to_string(),
+        value_code: code[9..11].to_string(),
+    })
+}
+
+/// Expand an 8-digit UPC-E code (number system, six digits and check
+/// digit) to the UPC-A code it represents. Only number systems 0 and 1
+/// exist for UPC-E, so any other leading digit gives `None`.
+fn expand_upce(upce: &[u8]) -> Option<String> {
+    if upce.len() != 8 || (upce[0] != b'0' && upce[0] != b'1') {
+        return None;
+    }
+    let (ns, d, check) = (upce[0], &upce[1..7], upce[7]);
+
+    let mut upca = Vec::with_capacity(12);
+    upca.push(ns);
+    match d[5] {
+        b'0'..=b'2' => {
+            upca.extend_from_slice(&d[..2]);
+            upca.push(d[5]);
+            upca.extend_from_slice(b"0000");
+            upca.extend_from_slice(&d[2..5]);
+        }
+        b'3' => {
+            upca.extend_from_slice(&d[..3]);
+            upca.extend_from_slice(b"00000");
+            upca.extend_from_slice(&d[3..5]);
+        }
+        b'4' => {
+            upca.extend_from_slice(&d[..4]);
+            upca.extend_from_slice(b"00000");
+            upca.push(d[4]);
+        }
+        _ => {
+            upca.extend_from_slice(&d[..5]);
+            upca.extend_from_slice(b"0000");
+            upca.push(d[5]);
+        }
+    }
+    upca.push(check);
+
+    String::from_utf8(upca).ok()
+}
+
+// Validate a code that must be exactly a UPC-A, without trimming or
+// padding, reporting why it is invalid.
+fn validate(code: &str) -> Result<(), FixError> {
+    if !code.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if code.len() > 12 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 12 {
+        return Err(FixError::TooShort);
+    }
+    if !utils::is_ascii_numeric(code) {
+        return Err(FixError::NonNumericString);
+    }
+    if !check(code) {
+        return Err(check_digit_error(code));
+    }
+
+    Ok(())
+}
+
+// Describe why a code of the full width failed `check`: either it is
+// not all digits, or its check digit is wrong.
+fn check_digit_error(code: &str) -> FixError {
+    if !utils::is_ascii_numeric(code) {
+        return FixError::NonNumericString;
+    }
+    let (expected, found) = utils::check_digit_pair(code.as_bytes());
+    FixError::CheckDigitIncorrect { expected, found }
+}
+
 #[cfg(test)]
 mod tests {
     use super::check;
+    use super::check_trimmed;
+    use super::check_with_separator;
+    use super::coupon_fields;
+    use super::expand_upce;
     use super::fix;
+    use super::fix_unambiguous;
+    use super::fix_with_input;
+    use super::format_grouped;
+    use super::is_coupon;
+    use super::number_system;
+    use super::number_system_kind;
+    use super::resolve_template;
+    use super::CouponFields;
     use super::FixError;
+    use super::Gtin12;
+    use super::NumberSystem;
+    use super::MAX_INPUT_LEN;
+    use gtin::{GtinError, GtinKind};
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
 
     #[test]
     fn check_valid() {
-        assert_eq!(check(&"000000000000"), true);
+        assert_eq!(check("000000000000"), true);
     }
 
     #[test]
@@ -139,6 +673,197 @@ mod tests {
         assert_eq!(check("999999999999"), false);
     }
 
+    #[test]
+    fn check_trimmed_whitespace() {
+        assert_eq!(check_trimmed("036000291452"), true);
+        assert_eq!(check_trimmed("036000291452\n"), true);
+        assert_eq!(check_trimmed("036000291452\r\n"), true);
+        assert_eq!(check_trimmed("\t036000291452 "), true);
+    }
+
+    #[test]
+    fn check_trimmed_invalid() {
+        assert_eq!(check_trimmed(""), false);
+        assert_eq!(check_trimmed("\n"), false);
+        assert_eq!(check_trimmed("36000291452\n"), false); // no padding
+        assert_eq!(check_trimmed("036000291453\n"), false);
+        assert_eq!(check_trimmed("0360002 91452"), false);
+        assert_eq!(check_trimmed("\u{a0}036000291452"), false); // not ASCII whitespace
+    }
+
+    #[test]
+    fn check_with_separator_present() {
+        assert_eq!(check_with_separator("03600029145-2", '-'), true);
+        assert_eq!(check_with_separator("03600029145 2", ' '), true);
+        assert_eq!(check_with_separator("03600029145-3", '-'), false);
+        assert_eq!(check_with_separator("3600029145-2", '-'), false); // no padding
+        assert_eq!(check_with_separator("03600029145❤2", '❤'), true);
+    }
+
+    #[test]
+    fn check_with_separator_absent() {
+        assert_eq!(check_with_separator("036000291452", '-'), true);
+        assert_eq!(check_with_separator("036000291453", '-'), false);
+        assert_eq!(check_with_separator("", '-'), false);
+        assert_eq!(check_with_separator("-", '-'), false);
+        assert_eq!(check_with_separator("03600029145-2", ' '), false);
+    }
+
+    #[test]
+    fn check_with_separator_misplaced() {
+        assert_eq!(check_with_separator("0360002914-52", '-'), false);
+        assert_eq!(check_with_separator("03600029145--2", '-'), false);
+        assert_eq!(check_with_separator("0-3600029145-2", '-'), false);
+        assert_eq!(check_with_separator("03600029145-❤", '-'), false);
+    }
+
+    #[test]
+    fn number_system_each_digit() {
+        let codes = [
+            "000000000000",
+            "100000000007",
+            "200000000004",
+            "300000000001",
+            "400000000008",
+            "500000000005",
+            "600000000002",
+            "700000000009",
+            "800000000006",
+            "900000000003",
+        ];
+        let kinds = [
+            NumberSystem::Regular,
+            NumberSystem::Regular,
+            NumberSystem::VariableWeight,
+            NumberSystem::Drug,
+            NumberSystem::InStore,
+            NumberSystem::Coupon,
+            NumberSystem::Regular,
+            NumberSystem::Regular,
+            NumberSystem::Regular,
+            NumberSystem::Coupon,
+        ];
+        for (digit, (code, kind)) in codes.iter().zip(kinds.iter()).enumerate() {
+            assert_eq!(number_system(code), Ok(digit as u8));
+            assert_eq!(number_system_kind(code), Ok(*kind));
+        }
+    }
+
+    #[test]
+    fn number_system_invalid() {
+        assert_eq!(
+            number_system("036000291453"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(number_system("36000291452"), Err(FixError::TooShort));
+        assert_eq!(number_system_kind("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn format_grouped_valid() {
+        assert_eq!(format_grouped("036000291452").unwrap(), "0 36000 29145 2");
+        assert_eq!(format_grouped("897854613315").unwrap(), "8 97854 61331 5");
+    }
+
+    #[test]
+    fn format_grouped_invalid() {
+        assert_eq!(
+            format_grouped("036000291453"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(format_grouped("36000291452"), Err(FixError::TooShort));
+        assert_eq!(
+            format_grouped("0036000291452"),
+            Err(FixError::TooLong { trimmed_len: 13 })
+        );
+        assert_eq!(
+            format_grouped("03600029145a"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(format_grouped("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn resolve_template_placeholder() {
+        assert_eq!(
+            resolve_template("03600029145?", '?').unwrap(),
+            "036000291452"
+        );
+        assert_eq!(
+            resolve_template("89785461331*", '*').unwrap(),
+            "897854613315"
+        );
+        assert_eq!(
+            resolve_template("00000000000?", '?').unwrap(),
+            "000000000000"
+        );
+    }
+
+    #[test]
+    fn resolve_template_digit() {
+        assert_eq!(
+            resolve_template("036000291452", '?').unwrap(),
+            "036000291452"
+        );
+        assert_eq!(
+            resolve_template("036000291453", '?'),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
+        // A different placeholder is not substituted
+        assert_eq!(
+            resolve_template("03600029145*", '?'),
+            Err(FixError::NonNumericString)
+        );
+    }
+
+    #[test]
+    fn resolve_template_invalid() {
+        assert_eq!(
+            resolve_template("3600029145?", '?'),
+            Err(FixError::TooShort)
+        );
+        assert_eq!(
+            resolve_template("003600029145?", '?'),
+            Err(FixError::TooLong { trimmed_len: 13 })
+        );
+        assert_eq!(
+            resolve_template("0360002914a?", '?'),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            resolve_template("0360002914❤?", '?'),
+            Err(FixError::NonAsciiString)
+        );
+        assert_eq!(
+            resolve_template("003600029145❤", '❤'),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+        assert_eq!(resolve_template("?", '?'), Err(FixError::TooShort));
+        assert_eq!(resolve_template("", '?'), Err(FixError::TooShort));
+    }
+
+    #[test]
+    fn resolve_template_digit_placeholder() {
+        // A wrong check digit must not be mistaken for the placeholder
+        assert_eq!(
+            resolve_template("036000291453", '3'),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            resolve_template("036000291452", '2'),
+            Err(FixError::NonNumericString)
+        );
+    }
+
     #[test]
     fn fix_non_ascii() {
         assert!(fix("❤").is_err());
@@ -146,12 +871,30 @@ mod tests {
 
     #[test]
     fn fix_too_long() {
-        assert_eq!(fix("0000000000000"), Err(FixError::TooLong));
+        assert_eq!(
+            fix("0000000000000"),
+            Err(FixError::TooLong { trimmed_len: 13 })
+        );
+        assert_eq!(
+            fix("1036000291452x"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(
+            fix("  1036000291452  "),
+            Err(FixError::TooLong { trimmed_len: 13 })
+        );
+        assert_eq!(fix("  036000291452  ").unwrap(), "036000291452");
     }
 
     #[test]
     fn fix_incorrect_check_digit() {
-        assert_eq!(fix("123456789013"), Err(FixError::CheckDigitIncorrect));
+        assert_eq!(
+            fix("123456789013"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
     }
 
     #[test]
@@ -160,10 +903,237 @@ mod tests {
         assert_eq!(fix("0").unwrap(), "000000000000");
     }
 
+    #[test]
+    fn fix_error_from_other_modules() {
+        use gtin14;
+        use gtin8;
+        assert_eq!(
+            FixError::from(gtin8::FixError::NonAsciiString),
+            FixError::NonAsciiString
+        );
+        assert_eq!(
+            FixError::from(gtin8::FixError::TooLong { trimmed_len: 15 }),
+            FixError::TooLong { trimmed_len: 15 }
+        );
+        let err: FixError = gtin14::FixError::CheckDigitIncorrect {
+            expected: 1,
+            found: 2,
+        }
+        .into();
+        assert_eq!(
+            err,
+            FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_valid() {
+        let code = Gtin12::try_from(&b"897854613315"[..]).unwrap();
+        assert_eq!(code.as_str(), "897854613315");
+        assert_eq!(Gtin12::try_from("897854613315"), Ok(code));
+    }
+
+    #[test]
+    fn try_from_bytes_invalid() {
+        assert_eq!(
+            Gtin12::try_from(&b"\xff\xfe"[..]),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin12),
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from("❤"),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin12),
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from("8978546133150"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin12),
+                trimmed_len: 13,
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from("97854613315"),
+            Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin12),
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from(" 97854613315"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin12),
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from("a97854613315"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin12),
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from("897854613316"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin12),
+                expected: 5,
+                found: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn is_coupon_number_system() {
+        assert_eq!(is_coupon("541000123455"), true);
+        assert_eq!(is_coupon("512345678900"), true);
+        assert_eq!(is_coupon("941000123453"), true);
+        assert_eq!(is_coupon("036000291452"), false);
+        assert_eq!(is_coupon("541000123456"), false);
+        assert_eq!(is_coupon(" 541000123455"), false);
+        assert_eq!(is_coupon("41000123455"), false);
+    }
+
+    #[test]
+    fn is_coupon_agrees_with_number_system_kind() {
+        for code in &[
+            "036000291452",
+            "212345012345",
+            "541000123455",
+            "941000123453",
+        ] {
+            assert_eq!(
+                is_coupon(code),
+                number_system_kind(code) == Ok(NumberSystem::Coupon)
+            );
+        }
+    }
+
+    #[test]
+    fn coupon_fields_layout() {
+        assert_eq!(
+            coupon_fields("536000999506"),
+            Some(CouponFields {
+                company_prefix: "36000".to_string(),
+                family_code: "999".to_string(),
+                value_code: "50".to_string(),
+            })
+        );
+        assert_eq!(coupon_fields("036000291452"), None);
+        assert_eq!(coupon_fields("941000123453"), None);
+        assert_eq!(coupon_fields("❤"), None);
+    }
+
+    #[test]
+    fn expand_upce_rules() {
+        assert_eq!(expand_upce(b"04252614").unwrap(), "042100005264");
+        assert_eq!(expand_upce(b"01234565").unwrap(), "012345000065");
+        assert_eq!(expand_upce(b"01234534").unwrap(), "012300000454");
+        assert_eq!(expand_upce(b"04963406").unwrap(), "049000006346");
+        assert_eq!(expand_upce(b"12345679").unwrap(), "123456000079");
+        assert_eq!(expand_upce(b"21234565"), None);
+        assert_eq!(expand_upce(b"0123456"), None);
+    }
+
+    #[test]
+    fn fix_unambiguous_accepts() {
+        assert_eq!(fix_unambiguous("036000291452").unwrap(), "036000291452");
+        assert_eq!(fix_unambiguous(" 36000291452").unwrap(), "036000291452");
+        assert_eq!(fix_unambiguous("00000000").unwrap(), "000000000000");
+        assert_eq!(fix_unambiguous("1234534").unwrap(), "000001234534");
+    }
+
+    #[test]
+    fn fix_unambiguous_rejects() {
+        assert_eq!(fix_unambiguous("01234565"), Err(FixError::Ambiguous));
+        assert_eq!(fix_unambiguous("1234565"), Err(FixError::Ambiguous));
+        assert_eq!(fix_unambiguous("79198"), Err(FixError::Ambiguous));
+        assert_eq!(
+            fix_unambiguous("036000291453"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(fix_unambiguous("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn fix_max_input_len() {
+        let mut padded = " ".repeat(MAX_INPUT_LEN - 12);
+        padded.push_str("036000291452");
+        assert!(fix(&padded).is_ok());
+        padded.push(' ');
+        assert_eq!(fix(&padded), Err(FixError::TooLong { trimmed_len: 12 }));
+        assert_eq!(
+            fix(" ".repeat(1 << 20)),
+            Err(FixError::TooLong { trimmed_len: 0 })
+        );
+    }
+
+    #[test]
+    fn fix_with_input_keeps_input() {
+        let kept = |input| {
+            if cfg!(feature = "error-input") {
+                Some(input)
+            } else {
+                None
+            }
+        };
+        assert_eq!(fix_with_input("036000291452").unwrap(), "036000291452");
+        let err = fix_with_input(" 036000291453\n").unwrap_err();
+        assert_eq!(err.input(), kept("036000291453"));
+        assert_eq!(
+            err.into_error(),
+            FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            }
+        );
+        assert_eq!(fix_with_input("❤").unwrap_err().input(), kept("❤"));
+
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(fix_with_input(&long).unwrap_err().input(), None);
+    }
+
     proptest! {
         #[test]
         fn doesnt_crash(ref s in ".*") {
-            check(s);
+            let _ = check(s);
+        }
+
+        #[test]
+        fn try_from_bytes_matches_check(ref bytes in prop::collection::vec(any::<u8>(), 0..20)) {
+            let valid = ::std::str::from_utf8(bytes).map(check).unwrap_or(false);
+            assert_eq!(Gtin12::try_from(&bytes[..]).is_ok(), valid);
+        }
+
+        #[test]
+        fn coupon_fields_doesnt_crash(ref s in ".*") {
+            assert_eq!(coupon_fields(s).is_some(), is_coupon(s));
+        }
+
+        #[test]
+        fn fix_unambiguous_agrees_with_fix(ref s in "[0-9]{0,13}") {
+            if let Ok(fixed) = fix_unambiguous(s) {
+                assert_eq!(fix(s), Ok(fixed));
+            }
+        }
+
+        #[test]
+        fn fix_doesnt_crash(ref bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+            if let Ok(fixed) = fix(String::from_utf8_lossy(bytes)) {
+                assert!(check(&fixed));
+            }
+        }
+
+        #[test]
+        fn fix_digits_doesnt_crash(ref s in "[0-9 \\t\\r\\n]{0,20}") {
+            if let Ok(fixed) = fix(s) {
+                assert!(check(&fixed));
+            }
         }
     }
 }
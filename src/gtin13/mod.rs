@@ -1,21 +1,140 @@
 //! Performs validation and correction of GTIN-13 and EAN-13 codes.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "futures")]
+use futures::{Stream, StreamExt};
+
+use context::InputError;
+use gtin12;
 use utils;
 
 /// Errors that make GTIN-13 correction impossible.
+///
+/// The `FixError` types of all GTIN modules share the same variants, so
+/// this type can be converted from any of them with `From`/`Into`. Each
+/// variant maps to the variant of the same name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FixError {
     /// The provided string contains non-ASCII characters.
     NonAsciiString,
+    /// The provided string contains characters other than ASCII digits.
+    NonNumericString,
     /// The provided code was too long to be valid.
-    TooLong,
+    TooLong {
+        /// The length of the code in bytes, after any whitespace
+        /// trimming done by the function returning the error. A length
+        /// within the expected width means that the input was rejected
+        /// only because of its surrounding whitespace.
+        trimmed_len: usize,
+    },
+    /// The provided code was too short to be valid.
+    TooShort,
     /// The calculated check-digit did not match the code's check-digit.
-    CheckDigitIncorrect,
+    CheckDigitIncorrect {
+        /// The check digit computed from the other digits of the code.
+        expected: u8,
+        /// The check digit the code ends with.
+        found: u8,
+    },
+    /// More than one correction of the code is possible, so none was
+    /// chosen.
+    Ambiguous,
+    /// The code is valid, but cannot be used for or converted into the
+    /// requested form.
+    NotRepresentable,
+}
+
+impl_fix_error_from!(gtin8, gtin12, gtin14);
+
+/// The longest input, in bytes, that [`fix`](fn.fix.html) will examine.
+///
+/// This is the 13-digit code plus an allowance for surrounding
+/// whitespace. Longer input is rejected with `TooLong` before it is
+/// copied, which bounds the work done on pathological input. The error
+/// still reports the trimmed length, so input that was only too long
+/// because of its whitespace can be told apart.
+pub const MAX_INPUT_LEN: usize = 13 + 32;
+
+/// The pairs of digits most often confused by optical character
+/// recognition, used by [`fix_ocr`](fn.fix_ocr.html): `0` and `8`, `1`
+/// and `7`, and `5` and `6`. Each pair is tried in both directions.
+pub const OCR_CONFUSIONS: &[(char, char)] = &[('0', '8'), ('1', '7'), ('5', '6')];
+
+gtin_newtype! {
+    /// A validated GTIN-13 (EAN-13) code.
+    ///
+    /// Values can only be constructed from input that passes
+    /// [`check`](fn.check.html), so the wrapped string is always a valid
+    /// code. Construct them with `TryFrom`, from either a `&str` or the raw
+    /// bytes of the code.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::gtin13::Gtin13;
+    /// use std::convert::TryFrom;
+    ///
+    /// let code = Gtin13::try_from(&b"1498279802125"[..]).unwrap();
+    /// assert_eq!(code.as_str(), "1498279802125");
+    /// assert_eq!(code.digit_at(0), Some(1));
+    /// assert_eq!(code.digit_at(12), Some(5)); // Check digit
+    /// assert_eq!(code.digit_at(13), None);
+    /// assert!(Gtin13::try_from("1498279802126").is_err()); // Bad check digit
+    /// ```
+    pub struct Gtin13(13);
+}
+
+/// The parity of a digit in the left half of an EAN-13 symbol.
+///
+/// Each of the six left-hand digits is encoded with one of two character
+/// sets, and the sequence of sets encodes the first digit of the code,
+/// which has no bars of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parity {
+    /// Set A (odd parity), also used by UPC-A.
+    L,
+    /// Set B (even parity).
+    G,
+}
+
+/// The logical structure of an EAN-13 symbol, as returned by
+/// [`structure`](fn.structure.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ean13Structure {
+    /// The first digit, encoded only by the parity of the left group.
+    pub first_digit: u8,
+    /// The six digits of the left group.
+    pub left: [u8; 6],
+    /// The parity of each digit of the left group.
+    pub parity: [Parity; 6],
+    /// The six digits of the right group, ending with the check digit.
+    /// These are always encoded with set C.
+    pub right: [u8; 6],
 }
 
+/// The parity pattern of the left group for each first digit.
+const PARITY_TABLE: [[Parity; 6]; 10] = {
+    use self::Parity::{G, L};
+    [
+        [L, L, L, L, L, L],
+        [L, L, G, L, G, G],
+        [L, L, G, G, L, G],
+        [L, L, G, G, G, L],
+        [L, G, L, L, G, G],
+        [L, G, G, L, L, G],
+        [L, G, G, G, L, L],
+        [L, G, L, G, L, G],
+        [L, G, L, G, G, L],
+        [L, G, G, L, G, L],
+    ]
+};
+
 /// Check that a GTIN-13 code is valid by checking the length (should be
 /// exactly 13 digits) and that the check-digit is correct.
 ///
+/// The code may be any string type, such as `&str`, `String` or
+/// `Cow<str>`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin13;
@@ -23,23 +142,236 @@ pub enum FixError {
 /// assert_eq!(gtin13::check("1498279802125"), true);  // Valid GTIN-13
 /// assert_eq!(gtin13::check("468712378699"), false);  // Too short
 /// assert_eq!(gtin13::check("1498279802124"), false); // Bad check digit
+/// assert_eq!(gtin13::check(String::from("1498279802125")), true);
 /// ```
-pub fn check(code: &str) -> bool {
-    if code.len() != 13 {
-        return false;
+#[must_use = "the result of the check tells whether the code is valid"]
+pub fn check(code: impl AsRef<str>) -> bool {
+    fn inner(code: &str) -> bool {
+        if code.is_empty() {
+            return false;
+        }
+        if code.len() != 13 {
+            return false;
+        }
+
+        // Validate the digits and accumulate the weighted sum in a single
+        // pass. Weighting 1, 3, 1, ... from the left puts weight 1 on the
+        // check digit, so a valid code sums to a multiple of 10.
+        let mut sum: u32 = 0;
+        for (i, &b) in code.as_bytes().iter().enumerate() {
+            let digit = b.wrapping_sub(b'0');
+            if digit > 9 {
+                return false;
+            }
+            sum += u32::from(digit) * if i & 1 == 0 { 1 } else { 3 };
+        }
+
+        sum.is_multiple_of(10)
     }
-    if !utils::is_ascii_numeric(code) {
+
+    inner(code.as_ref())
+}
+
+/// Check the GTIN-13 code in the 13 bytes starting at `offset` within a
+/// larger record, such as a line of a fixed-width file.
+///
+/// The field is validated in place, as with [`check`](fn.check.html),
+/// without copying it out of the record. Returns false if the record is
+/// too short to hold the field at `offset`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let record = "ITEM0042 4006381333931 EA 000012";
+/// assert_eq!(gtin13::check_at(record, 9), true);
+/// assert_eq!(gtin13::check_at(record, 8), false); // Misaligned
+/// assert_eq!(gtin13::check_at(record, 30), false); // Past the end
+/// ```
+pub fn check_at(record: &str, offset: usize) -> bool {
+    let field = offset
+        .checked_add(13)
+        .and_then(|end| record.get(offset..end));
+    match field {
+        Some(field) => check(field),
+        None => false,
+    }
+}
+
+/// Fix the GTIN-13 code in the 13 bytes starting at `offset` within a
+/// larger record, as [`fix`](fn.fix.html) does.
+///
+/// Fixed-width fields are often padded with spaces, so a field holding a
+/// right-aligned, shortened code such as `"  36000291452"` is repaired
+/// to `0036000291452`. Returns `TooShort` if the record is too short to
+/// hold the field at `offset`, and `NonAsciiString` if the field does not
+/// start and end on character boundaries.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let record = "ITEM0043   36000291452 EA 000001";
+/// assert_eq!(gtin13::fix_at(record, 9).unwrap(), "0036000291452");
+/// assert!(gtin13::fix_at(record, 30).is_err()); // Past the end
+/// ```
+pub fn fix_at(record: &str, offset: usize) -> Result<String, FixError> {
+    let end = match offset.checked_add(13) {
+        Some(end) if end <= record.len() => end,
+        _ => return Err(FixError::TooShort),
+    };
+    match record.get(offset..end) {
+        Some(field) => fix(field),
+        None => Err(FixError::NonAsciiString),
+    }
+}
+
+/// Check that a GTIN-13 code is valid, using `map` to translate each
+/// character to a digit value.
+///
+/// This allows codes written with digits other than ASCII, such as the
+/// full-width forms `'０'` to `'９'` or another script's decimal digits,
+/// to be validated in a way the caller controls. The code must have
+/// exactly 13 characters and `map` must return a value from 0 to 9 for
+/// every one of them; any other result makes the code invalid. The
+/// length and check-digit rules are the same as for
+/// [`check`](fn.check.html), which remains strictly ASCII.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let full_width = |c: char| match c {
+///     '０'..='９' => Some((c as u32 - '０' as u32) as u8),
+///     _ => c.to_digit(10).map(|d| d as u8),
+/// };
+/// assert_eq!(gtin13::check_with_mapper("１４９８２７９８０２１２５", full_width), true);
+/// assert_eq!(gtin13::check_with_mapper("１４９８２７９８０２１２４", full_width), false);
+/// assert_eq!(gtin13::check_with_mapper("1498279802125", full_width), true);
+/// ```
+pub fn check_with_mapper(code: &str, map: impl Fn(char) -> Option<u8>) -> bool {
+    let mut digits = [0u8; 13];
+    let mut len = 0;
+    for c in code.chars() {
+        if len == digits.len() {
+            return false;
+        }
+        match map(c) {
+            Some(d) if d <= 9 => digits[len] = b'0' + d,
+            _ => return false,
+        }
+        len += 1;
+    }
+    if len != digits.len() {
         return false;
     }
 
-    // Calculate and compare check digit
-    let bytes = code.as_bytes();
-    let check = utils::compute_check_digit(bytes);
-    if check != bytes[12] - 48 {
+    utils::compute_check_digit(&digits) == digits[12] - b'0'
+}
+
+/// Check a GTIN-13 code given as digit values (0 to 9) rather than as a
+/// string.
+///
+/// The iterator must yield exactly 13 values, the last being the check
+/// digit. Too few or too many values, or any value above 9, make the
+/// code invalid. No string is built, so this suits callers that already
+/// hold decoded digits, such as the output of a barcode decoder.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let digits = [4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3, 1];
+/// assert_eq!(gtin13::check_digits(digits.iter().cloned()), true);
+/// assert_eq!(gtin13::check_digits(digits[..12].iter().cloned()), false);
+/// ```
+pub fn check_digits<I: Iterator<Item = u8>>(digits: I) -> bool {
+    let mut code = [0u8; 13];
+    let mut len = 0;
+    for digit in digits {
+        if len == code.len() || digit > 9 {
+            return false;
+        }
+        code[len] = b'0' + digit;
+        len += 1;
+    }
+    if len != code.len() {
         return false;
     }
 
-    true
+    utils::compute_check_digit(&code) == code[12] - b'0'
+}
+
+/// Check that a GTIN-13 code is valid and starts with one of the allowed
+/// GS1 company prefixes.
+///
+/// This catches codes that pass [`check`](fn.check.html) but belong to a
+/// different company, such as a mis-keyed code that happens to have a
+/// valid check digit. An empty `prefixes` list allows any prefix. The
+/// code is not trimmed or padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let ours = &["4006381", "4012345"];
+/// assert_eq!(gtin13::check_with_prefixes("4006381333931", ours), true);
+/// assert_eq!(gtin13::check_with_prefixes("1498279802125", ours), false); // Not ours
+/// assert_eq!(gtin13::check_with_prefixes("4006381333932", ours), false); // Bad check digit
+/// assert_eq!(gtin13::check_with_prefixes("1498279802125", &[]), true);
+/// ```
+pub fn check_with_prefixes(code: &str, prefixes: &[&str]) -> bool {
+    check(code) && (prefixes.is_empty() || prefixes.iter().any(|p| code.starts_with(p)))
+}
+
+/// Check a GTIN-13 code as [`check`](fn.check.html) does, returning every
+/// reason the code is invalid rather than just the first.
+///
+/// The code is not trimmed or padded. The returned errors are in this
+/// order, each appearing at most once:
+///
+/// * `NonAsciiString` if the code contains non-ASCII characters.
+/// * `TooLong` or `TooShort` if the code is not 13 bytes long.
+/// * `NonNumericString` if the code contains ASCII characters other than
+///   digits.
+/// * `CheckDigitIncorrect` if the code is otherwise valid but its check
+///   digit is wrong. The check digit is only meaningful for 13 digits,
+///   so it is not reported alongside any other error.
+///
+/// A valid code gives an empty vector.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+/// use gtin_validate::gtin13::FixError;
+///
+/// assert_eq!(gtin13::validate_all("4006381333931"), vec![]);
+/// assert_eq!(
+///     gtin13::validate_all("40063813339310A"),
+///     vec![FixError::TooLong { trimmed_len: 15 }, FixError::NonNumericString]
+/// );
+/// ```
+pub fn validate_all(code: &str) -> Vec<FixError> {
+    let mut errors = Vec::new();
+
+    if !code.is_ascii() {
+        errors.push(FixError::NonAsciiString);
+    }
+    if code.len() > 13 {
+        errors.push(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    } else if code.len() < 13 {
+        errors.push(FixError::TooShort);
+    }
+    if code.bytes().any(|b| b.is_ascii() && !b.is_ascii_digit()) {
+        errors.push(FixError::NonNumericString);
+    }
+    if errors.is_empty() && !check(code) {
+        errors.push(check_digit_error(code));
+    }
+
+    errors
 }
 
 /// Attempt to fix an invalid GTIN-13 code by stripping whitespace from
@@ -50,6 +382,10 @@ pub fn check(code: &str) -> bool {
 /// entry and software that treats GTINs as integers rather than strings,
 /// thus truncating the leading zeros.
 ///
+/// As with [`check`](fn.check.html), the code may be any string type.
+/// Input longer than [`MAX_INPUT_LEN`](constant.MAX_INPUT_LEN.html)
+/// bytes is rejected with `TooLong`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin13;
@@ -74,106 +410,2077 @@ pub fn check(code: &str) -> bool {
 ///   Err(_) => {println!("UPC is invalid");}
 /// }
 /// ```
+///
+/// A wrong check digit is reported together with the digit that was
+/// expected, leaving the choice of rewriting it to the caller:
+///
+/// ```
+/// use gtin_validate::gtin13::{self, FixError};
+///
+/// assert_eq!(
+///     gtin13::fix("4006381333932"),
+///     Err(FixError::CheckDigitIncorrect { expected: 1, found: 2 })
+/// );
+/// ```
+#[must_use = "the fixed code is returned rather than modified in place"]
+pub fn fix(code: impl AsRef<str>) -> Result<String, FixError> {
+    #[inline]
+    fn inner(code: &str) -> Result<String, FixError> {
+        if code.len() <= MAX_INPUT_LEN {
+            let trimmed = code.trim();
+            if trimmed.len() == 13 {
+                validate(trimmed)?;
+                return Ok(trimmed.to_string());
+            }
+        }
+
+        fix_padded(code)
+    }
+
+    inner(code.as_ref())
+}
+
+// Codes that need padding are the uncommon case, so keep this path out
+// of line to leave the full-length path in `fix` small.
+#[cold]
+#[inline(never)]
+fn fix_padded(code: &str) -> Result<String, FixError> {
+    let digits = fix_digits(code)?;
+    Ok(digits.iter().map(|&b| char::from(b)).collect())
+}
 
-pub fn fix(code: &str) -> Result<String, FixError> {
-    let mut fixed = code.trim().to_string();
+// Trim, zero-pad and validate a code, returning its digits. The variants
+// of `fix` differ only in where they write the result, so they all call
+// this to behave identically; `fix` itself only skips it for full-length
+// codes, which `validate` checks the same way.
+#[inline]
+fn fix_digits(code: &str) -> Result<[u8; 13], FixError> {
+    if code.len() > MAX_INPUT_LEN {
+        return Err(FixError::TooLong {
+            trimmed_len: code.trim().len(),
+        });
+    }
+    let trimmed = code.trim();
 
-    if !fixed.is_ascii() {
+    if !trimmed.is_ascii() {
         return Err(FixError::NonAsciiString);
     }
-    if fixed.len() > 13 {
-        return Err(FixError::TooLong);
+    if trimmed.len() > 13 {
+        return Err(FixError::TooLong {
+            trimmed_len: trimmed.len(),
+        });
+    }
+
+    let mut digits = [b'0'; 13];
+    digits[13 - trimmed.len()..].copy_from_slice(trimmed.as_bytes());
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return Err(FixError::NonNumericString);
     }
-    fixed = utils::zero_pad(fixed, 13);
-    if !check(&fixed) {
-        return Err(FixError::CheckDigitIncorrect);
+    let (expected, found) = utils::check_digit_pair(&digits);
+    if expected != found {
+        return Err(FixError::CheckDigitIncorrect { expected, found });
     }
 
-    Ok(fixed)
+    Ok(digits)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::check;
-    use super::fix;
-    use super::FixError;
+/// Fix a GTIN-13 code as [`fix`](fn.fix.html) does, keeping a copy of the
+/// trimmed input in the error.
+///
+/// The copy is only made when `fix` fails, and only with the
+/// `error-input` feature. See [`InputError`](../struct.InputError.html).
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let err = gtin13::fix_with_input("4006381333932 ").unwrap_err();
+/// #[cfg(feature = "error-input")]
+/// assert_eq!(err.input(), Some("4006381333932"));
+/// ```
+pub fn fix_with_input(code: &str) -> Result<String, InputError<FixError>> {
+    fix(code).map_err(|error| InputError::new(error, code, MAX_INPUT_LEN))
+}
 
-    #[test]
-    fn check_valid() {
-        assert_eq!(check("0000000000000"), true);
-        assert_eq!(check("8845791354268"), true);
-        assert_eq!(check("0334873614126"), true);
-    }
+/// Fix a GTIN-13 code as [`fix`](fn.fix.html) does, discarding the
+/// error.
+///
+/// This is the lossy form of `fix` for pipelines that keep what can be
+/// repaired and drop the rest, such as with `filter_map` over a column.
+/// Use `fix` or [`fix_with_input`](fn.fix_with_input.html) where the
+/// reason a code was dropped needs to be reported.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let column = ["4006381333931", " 495205944325", "4006381333932", "❤"];
+/// let fixed: Vec<String> = column.iter().filter_map(|code| gtin13::fix_or_skip(code)).collect();
+/// assert_eq!(fixed, ["4006381333931", "0495205944325"]);
+/// ```
+pub fn fix_or_skip(code: &str) -> Option<String> {
+    fix(code).ok()
+}
 
-    #[test]
-    fn check_invalid_length() {
-        assert_eq!(check("000"), false);
-        assert_eq!(check("00000000000000"), false);
+/// Fix a GTIN-13 code as [`fix`](fn.fix.html) does, first removing a
+/// single leading apostrophe.
+///
+/// Spreadsheet software such as Excel often exports numbers formatted as
+/// text with a leading `'` (as in `'036000291452`) to stop them being
+/// read back as integers. After stripping whitespace from the left and
+/// right sides, one such apostrophe is removed and the rest of the code
+/// is fixed as usual. The strict `fix` rejects the apostrophe.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::fix_lenient("'036000291452").unwrap(), "0036000291452");
+/// assert_eq!(gtin13::fix_lenient("4006381333931").unwrap(), "4006381333931");
+/// assert!(gtin13::fix("'036000291452").is_err());
+/// assert!(gtin13::fix_lenient("''036000291452").is_err());
+/// ```
+#[must_use = "the fixed code is returned rather than modified in place"]
+pub fn fix_lenient(code: &str) -> Result<String, FixError> {
+    let trimmed = code.trim();
+    fix(trimmed.strip_prefix('\'').unwrap_or(trimmed))
+}
+
+/// The non-ASCII separators removed by
+/// [`fix_unicode`](fn.fix_unicode.html).
+///
+/// These are the spaces and dashes that commonly appear in codes copied
+/// from PDFs and word processors:
+///
+/// * U+00A0 NO-BREAK SPACE
+/// * U+2007 FIGURE SPACE
+/// * U+2009 THIN SPACE
+/// * U+202F NARROW NO-BREAK SPACE
+/// * U+2010 HYPHEN
+/// * U+2011 NON-BREAKING HYPHEN
+/// * U+2012 FIGURE DASH
+/// * U+2013 EN DASH
+pub const UNICODE_SEPARATORS: &[char] = &[
+    '\u{a0}', '\u{2007}', '\u{2009}', '\u{202f}', '\u{2010}', '\u{2011}', '\u{2012}', '\u{2013}',
+];
+
+/// Fix a GTIN-13 code as [`fix`](fn.fix.html) does, first removing the
+/// non-ASCII separators listed in
+/// [`UNICODE_SEPARATORS`](constant.UNICODE_SEPARATORS.html).
+///
+/// Codes pasted from PDFs often contain figure dashes, non-breaking
+/// hyphens or similar characters that look like ASCII separators but
+/// make `fix` fail with `NonAsciiString`. These are removed wherever they
+/// appear. The list is deliberately short: any other non-ASCII character
+/// is still rejected, and ASCII characters, including `-` and space, are
+/// left for `fix` to judge. ASCII input is passed to `fix` without being
+/// copied.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::fix_unicode("400638\u{2012}1333931").unwrap(), "4006381333931");
+/// assert_eq!(gtin13::fix_unicode("4006381333931").unwrap(), "4006381333931");
+/// assert!(gtin13::fix("400638\u{2012}1333931").is_err());
+/// ```
+pub fn fix_unicode(code: &str) -> Result<String, FixError> {
+    if code.is_ascii() {
+        return fix(code);
+    }
+    if code.len() > MAX_INPUT_LEN {
+        return Err(FixError::TooLong {
+            trimmed_len: code.trim().len(),
+        });
     }
 
-    #[test]
-    fn check_non_ascii() {
-        assert_eq!(check("❤"), false);
+    let cleaned: String = code
+        .chars()
+        .filter(|c| !UNICODE_SEPARATORS.contains(c))
+        .collect();
+    fix(cleaned)
+}
+
+/// Cumulative counts of the outcomes of
+/// [`fix_counting`](fn.fix_counting.html).
+///
+/// The counters are atomic and updated with relaxed ordering, so a
+/// single `FixStats` can be shared between threads (it is `Sync`) at
+/// negligible cost. Each counter is exact, but a snapshot of several
+/// counters taken while other threads are counting may not be
+/// consistent with one another.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let stats = gtin13::FixStats::new();
+/// for code in &["4006381333931", "495205944325", "4006381333932"] {
+///     let _ = gtin13::fix_counting(code, &stats);
+/// }
+/// assert_eq!(stats.already_valid(), 1);
+/// assert_eq!(stats.repaired(), 1);
+/// assert_eq!(stats.failed(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct FixStats {
+    already_valid: AtomicUsize,
+    repaired: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+impl FixStats {
+    /// Create a set of counters, all zero.
+    pub fn new() -> FixStats {
+        FixStats::default()
     }
 
-    #[test]
-    fn check_non_numeric() {
-        assert_eq!(check("a"), false);
-        assert_eq!(check("abcdabcdabcda"), false); // length 13
-        assert_eq!(check("000000000000a"), false); // invalid check digit
+    /// Return the number of codes that were already valid.
+    pub fn already_valid(&self) -> usize {
+        self.already_valid.load(Ordering::Relaxed)
     }
 
-    #[test]
-    fn check_invalid_check_digit() {
-        assert_eq!(check("0000000000001"), false);
-        assert_eq!(check("0000000000002"), false);
-        assert_eq!(check("0000000000003"), false);
-        assert_eq!(check("0000000000004"), false);
-        assert_eq!(check("0000000000005"), false);
-        assert_eq!(check("0000000000006"), false);
-        assert_eq!(check("0000000000007"), false);
-        assert_eq!(check("0000000000008"), false);
-        assert_eq!(check("0000000000009"), false);
+    /// Return the number of codes that were invalid but could be fixed.
+    pub fn repaired(&self) -> usize {
+        self.repaired.load(Ordering::Relaxed)
     }
 
-    #[test]
-    fn check_static_data() {
-        assert_eq!(check("0000000000000"), true);
-        assert_eq!(check("0123456789012"), true);
-        assert_eq!(check("0123456789013"), false);
-        assert_eq!(check("0999999999993"), true);
-        assert_eq!(check("0999999999999"), false);
-        assert_eq!(check("4459121265748"), true);
-        assert_eq!(check("4459121265747"), false);
+    /// Return the number of codes that could not be fixed.
+    pub fn failed(&self) -> usize {
+        self.failed.load(Ordering::Relaxed)
     }
+}
 
-    #[test]
-    fn fix_non_ascii() {
-        assert!(fix("❤").is_err());
+/// Fix a GTIN-13 code with [`fix`](fn.fix.html), counting the outcome in
+/// `stats`.
+///
+/// A code counts as already valid when `fix` returns it unchanged, as
+/// repaired when `fix` had to change it, and as failed when `fix`
+/// returned an error.
+pub fn fix_counting(code: &str, stats: &FixStats) -> Result<String, FixError> {
+    let result = fix(code);
+    let counter = match result {
+        Ok(ref fixed) if fixed == code => &stats.already_valid,
+        Ok(_) => &stats.repaired,
+        Err(_) => &stats.failed,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+    result
+}
+
+/// Fix a GTIN-13 code with [`fix`](fn.fix.html), also reporting whether
+/// the fixed code differs from the input.
+///
+/// The flag is true when whitespace was stripped or zero-padding was
+/// added, which is all an importer needs to mark a row as dirty.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(
+///     gtin13::fix_was_modified("4006381333931").unwrap(),
+///     ("4006381333931".to_string(), false)
+/// );
+/// assert_eq!(
+///     gtin13::fix_was_modified("495205944325").unwrap(),
+///     ("0495205944325".to_string(), true)
+/// );
+/// ```
+pub fn fix_was_modified(code: &str) -> Result<(String, bool), FixError> {
+    let fixed = fix(code)?;
+    let modified = fixed != code;
+    Ok((fixed, modified))
+}
+
+/// A reusable buffer for fixing many GTIN-13 codes without allocating.
+///
+/// [`Fixer::fix`](struct.Fixer.html#method.fix) behaves like
+/// [`fix`](fn.fix.html), but writes the fixed code into a buffer owned by
+/// the `Fixer` and returns a borrow of it. The buffer is sized for a
+/// GTIN-13 when the `Fixer` is created, so no call allocates.
+///
+/// The returned `&str` borrows the `Fixer` mutably, so it must be dropped
+/// (or copied) before the next call to `fix`; the borrow checker enforces
+/// this.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let mut fixer = gtin13::Fixer::new();
+/// assert_eq!(fixer.fix(" 4006381333931\n"), Ok("4006381333931"));
+/// assert_eq!(fixer.fix("495205944325"), Ok("0495205944325"));
+/// assert!(fixer.fix("4006381333932").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fixer {
+    buffer: String,
+}
+
+impl Fixer {
+    /// Create a `Fixer` with a buffer large enough for a GTIN-13.
+    pub fn new() -> Fixer {
+        Fixer {
+            buffer: String::with_capacity(13),
+        }
     }
 
+    /// Fix a GTIN-13 code as [`fix`](fn.fix.html) does, returning the
+    /// fixed code from the internal buffer. The result is only valid
+    /// until the next call.
+    pub fn fix(&mut self, code: &str) -> Result<&str, FixError> {
+        let digits = fix_digits(code)?;
 
-    #[test]
-    fn fix_too_long() {
-        assert_eq!(fix("00000000000000"), Err(FixError::TooLong));
+        self.buffer.clear();
+        self.buffer.extend(digits.iter().map(|&b| char::from(b)));
+        Ok(&self.buffer)
     }
+}
 
-    #[test]
-    fn fix_incorrect_check_digit() {
-        assert_eq!(fix("8845791354262"), Err(FixError::CheckDigitIncorrect));
+impl Default for Fixer {
+    fn default() -> Fixer {
+        Fixer::new()
     }
+}
 
-    #[test]
-    fn fix_needs_zero_padding() {
-        assert!(fix("0").is_ok());
-        assert_eq!(fix("0").unwrap(), "0000000000000");
-        assert_eq!(fix("123012301238").unwrap(), "0123012301238");
+/// How usable a GTIN-13 code is, as determined by
+/// [`classify`](fn.classify.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Validity {
+    /// The code passes [`check`](fn.check.html) as it is.
+    Valid,
+    /// The code fails `check`, but [`fix`](fn.fix.html) can correct it.
+    Repairable,
+    /// The code can be neither checked nor fixed.
+    Invalid,
+}
+
+/// Classify a GTIN-13 code as valid, repairable or invalid, by trying
+/// [`check`](fn.check.html) and then [`fix`](fn.fix.html).
+///
+/// This suits interfaces that show a three-state indicator for each
+/// code; call `fix` to obtain the repaired code.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13::{self, Validity};
+///
+/// assert_eq!(gtin13::classify("4006381333931"), Validity::Valid);
+/// assert_eq!(gtin13::classify("495205944325"), Validity::Repairable);
+/// assert_eq!(gtin13::classify("4006381333932"), Validity::Invalid);
+/// ```
+pub fn classify(code: &str) -> Validity {
+    if check(code) {
+        Validity::Valid
+    } else if fix(code).is_ok() {
+        Validity::Repairable
+    } else {
+        Validity::Invalid
     }
+}
 
-    proptest! {
-        #[test]
-        fn doesnt_crash(ref s in ".*") {
-            check(s);
+/// Zero-pad a GTIN-13 code that is less than 13 digits in length and
+/// check that the result is valid.
+///
+/// This is [`fix`](fn.fix.html) without the whitespace stripping, for
+/// input that has already been cleaned. Any character other than an
+/// ASCII digit, including whitespace, is rejected.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::pad_and_check("495205944325").unwrap(), "0495205944325");
+/// assert!(gtin13::pad_and_check("495205944325 ").is_err()); // Not trimmed
+/// assert!(gtin13::fix("495205944325 ").is_ok());
+/// ```
+pub fn pad_and_check(code: &str) -> Result<String, FixError> {
+    let padded = utils::zero_pad(code, 13);
+    validate(&padded)?;
+
+    Ok(padded.into_owned())
+}
+
+/// Parse a GTIN-13 code, also accepting a UPC-A (GTIN-12) code and
+/// upgrading it to GTIN-13.
+///
+/// Every UPC-A code is a GTIN-13 with a leading zero, so a valid 12-digit
+/// UPC-A is returned with `0` prepended. Any other input must be a valid
+/// GTIN-13. Whitespace is stripped from the left and right sides, but no
+/// other zero-padding is performed.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::parse_accepting_upca("036000291452").unwrap(), "0036000291452");
+/// assert_eq!(gtin13::parse_accepting_upca("4006381333931").unwrap(), "4006381333931");
+/// assert!(gtin13::parse_accepting_upca("036000291453").is_err()); // Bad check digit
+/// ```
+pub fn parse_accepting_upca(code: &str) -> Result<String, FixError> {
+    let code = code.trim();
+    if gtin12::check(code) {
+        let mut upgraded = String::with_capacity(13);
+        upgraded.push('0');
+        upgraded.push_str(code);
+        return Ok(upgraded);
+    }
+
+    if !code.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if code.len() > 13 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 12 {
+        return Err(FixError::TooShort);
+    }
+    if !utils::is_ascii_numeric(code) {
+        return Err(FixError::NonNumericString);
+    }
+    if !check(code) {
+        return Err(check_digit_error(code));
+    }
+
+    Ok(code.to_string())
+}
+
+/// Convert a GTIN-13 code with a leading zero to the UPC-A (GTIN-12) it
+/// represents, the inverse of
+/// [`parse_accepting_upca`](fn.parse_accepting_upca.html).
+///
+/// The code is corrected with [`fix`](fn.fix.html) first, then its
+/// leading zero is removed. The check digit is weighted from the right,
+/// so removing the zero leaves it unchanged and the result always passes
+/// [`gtin12::check`](../gtin12/fn.check.html). A code with any other
+/// leading digit is not a UPC-A, and gives `NotRepresentable`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+/// use gtin_validate::gtin13::FixError;
+///
+/// assert_eq!(gtin13::to_gtin12("0036000291452").unwrap(), "036000291452");
+/// assert_eq!(gtin13::to_gtin12("4006381333931"), Err(FixError::NotRepresentable));
+/// ```
+pub fn to_gtin12(code: &str) -> Result<String, FixError> {
+    let mut fixed = fix(code)?;
+    if !fixed.starts_with('0') {
+        return Err(FixError::NotRepresentable);
+    }
+
+    fixed.remove(0);
+    Ok(fixed)
+}
+
+/// Compute the check digit for the first 12 digits of a GTIN-13 code,
+/// such as a code that is still being typed.
+///
+/// The check digit is returned only when `partial` is exactly 12 ASCII
+/// digits. Any other input (too short, too long, or non-numeric) gives
+/// `None` rather than an error, so this is cheap to call on every
+/// keystroke.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::check_digit_for_prefix("400638"), None); // Still typing
+/// assert_eq!(gtin13::check_digit_for_prefix("400638133393"), Some(1));
+/// assert_eq!(gtin13::check_digit_for_prefix("4006381333931"), None); // Too long
+/// ```
+pub fn check_digit_for_prefix(partial: &str) -> Option<u8> {
+    if partial.len() != 12 || !utils::is_ascii_numeric(partial) {
+        return None;
+    }
+
+    // compute_check_digit skips the last byte, which is where the check
+    // digit would go, so reserve a placeholder for it.
+    let mut bytes = [b'0'; 13];
+    bytes[..12].copy_from_slice(partial.as_bytes());
+    Some(utils::compute_check_digit(&bytes))
+}
+
+/// Return the check digit computed from the first 12 digits of a GTIN-13
+/// code together with the check digit actually provided, as
+/// `(computed, provided)`.
+///
+/// The two values are not compared, so the code need not be valid; this
+/// is meant for displaying both side by side. The code must be exactly
+/// 13 ASCII digits and is not trimmed.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::check_digit_diagnostic("4006381333931"), Ok((1, 1)));
+/// assert_eq!(gtin13::check_digit_diagnostic("4006381333937"), Ok((1, 7)));
+/// ```
+pub fn check_digit_diagnostic(code: &str) -> Result<(u8, u8), FixError> {
+    match validate(code) {
+        Ok(()) | Err(FixError::CheckDigitIncorrect { .. }) => {}
+        Err(err) => return Err(err),
+    }
+
+    let bytes = code.as_bytes();
+    Ok((utils::compute_check_digit(bytes), bytes[12] - b'0'))
+}
+
+/// Assemble a GTIN-13 code from a GS1 company prefix and a product code,
+/// computing the check digit.
+///
+/// The prefix and product code together must be exactly 12 ASCII digits.
+/// This is the usual way of numbering products once a company prefix has
+/// been assigned.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::build("4006381", "33393").unwrap(), "4006381333931");
+/// assert_eq!(gtin13::build("4006381", "3339"), Err(gtin13::FixError::TooShort));
+/// assert_eq!(
+///     gtin13::build("4006381", "3339A"),
+///     Err(gtin13::FixError::NonNumericString)
+/// );
+/// ```
+pub fn build(gs1_prefix: &str, product: &str) -> Result<String, FixError> {
+    let mut code = String::with_capacity(13);
+    code.push_str(gs1_prefix);
+    code.push_str(product);
+
+    if !code.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if !utils::is_ascii_numeric(&code) {
+        return Err(FixError::NonNumericString);
+    }
+    if code.len() > 12 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 12 {
+        return Err(FixError::TooShort);
+    }
+
+    let check = check_digit_for_prefix(&code).ok_or(FixError::NonNumericString)?;
+    code.push(char::from(b'0' + check));
+    Ok(code)
+}
+
+/// Remove a leading AIM symbology identifier of the EAN/UPC family from
+/// raw scanner output.
+///
+/// Some scanners prefix the decoded digits with a three-character
+/// symbology identifier. The recognized identifiers are:
+///
+/// * `]E0`: EAN-13, UPC-A or UPC-E without an add-on
+/// * `]E1`: two-digit add-on on its own
+/// * `]E2`: five-digit add-on on its own
+/// * `]E3`: EAN-13, UPC-A or UPC-E with an add-on
+/// * `]E4`: EAN-8
+///
+/// Input without one of these prefixes is returned unchanged. No
+/// validation is performed; pass the result to `check` or `fix`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// let code = gtin13::strip_aim_identifier("]E04006381333931");
+/// assert_eq!(code, "4006381333931");
+/// assert_eq!(gtin13::check(code), true);
+///
+/// assert_eq!(gtin13::strip_aim_identifier("4006381333931"), "4006381333931");
+/// ```
+pub fn strip_aim_identifier(raw: &str) -> &str {
+    let bytes = raw.as_bytes();
+    if bytes.len() >= 3 && bytes[0] == b']' && bytes[1] == b'E' && (b'0'..=b'4').contains(&bytes[2])
+    {
+        &raw[3..]
+    } else {
+        raw
+    }
+}
+
+/// Check a GTIN-13 code followed directly by a two- or five-digit
+/// add-on, as some scanners transmit them without a separator.
+///
+/// The input is split by its length: 13 characters are a code without
+/// an add-on, 15 a code with a two-digit add-on and 18 a code with a
+/// five-digit add-on. The code must pass [`check`](fn.check.html) and
+/// the add-on must be ASCII digits. Other lengths are rejected.
+///
+/// Unlike the main code, an add-on carries no check digit: its checksum
+/// is encoded in the parity of its bars, which the scanner verifies
+/// while decoding. Every string of digits is therefore a valid add-on
+/// once transmitted.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::check_with_addon("4006381333931"), true);
+/// assert_eq!(gtin13::check_with_addon("400638133393112"), true);
+/// assert_eq!(gtin13::check_with_addon("400638133393152495"), true);
+/// assert_eq!(gtin13::check_with_addon("400638133393252495"), false); // Bad check digit
+/// assert_eq!(gtin13::check_with_addon("4006381333931123"), false);   // No such add-on
+/// ```
+pub fn check_with_addon(raw: &str) -> bool {
+    match raw.len() {
+        13 => check(raw),
+        15 | 18 => raw.is_ascii() && check(&raw[..13]) && utils::is_ascii_numeric(&raw[13..]),
+        _ => false,
+    }
+}
+
+/// Attempt to repair a GTIN-13 code in which two adjacent digits were
+/// swapped, a common mistake during manual keying.
+///
+/// A valid code is returned unchanged. For a 13-digit code that fails
+/// [`check`](fn.check.html), every swap of two adjacent digits is tried;
+/// if exactly one of them produces a valid code, that code is returned.
+/// If several different swaps produce a valid code, the intended one
+/// cannot be determined and `FixError::Ambiguous` is returned. If no swap
+/// helps, `FixError::CheckDigitIncorrect` is returned with the check
+/// digits of the input as given.
+///
+/// Unlike [`fix`](fn.fix.html), no whitespace is stripped and no padding
+/// is performed, so the input must be exactly 13 digits.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::repair_transposition("6507704999626").unwrap(), "6570704999626");
+/// assert_eq!(
+///     gtin13::repair_transposition("0406381333931"),
+///     Err(gtin13::FixError::Ambiguous)
+/// );
+/// ```
+pub fn repair_transposition(code: &str) -> Result<String, FixError> {
+    match validate(code) {
+        Ok(()) => return Ok(code.to_string()),
+        Err(FixError::CheckDigitIncorrect { .. }) => {}
+        Err(err) => return Err(err),
+    }
+
+    let mut repaired: Option<String> = None;
+    let mut bytes = code.as_bytes().to_vec();
+    for i in 0..12 {
+        if bytes[i] == bytes[i + 1] {
+            continue;
+        }
+        bytes.swap(i, i + 1);
+        let candidate: String = bytes.iter().map(|&b| char::from(b)).collect();
+        bytes.swap(i, i + 1);
+
+        if check(&candidate) {
+            match repaired {
+                Some(ref previous) if *previous != candidate => {
+                    return Err(FixError::Ambiguous);
+                }
+                _ => repaired = Some(candidate),
+            }
+        }
+    }
+
+    repaired.ok_or_else(|| check_digit_error(code))
+}
+
+/// Attempt to repair a GTIN-13 code read by optical character
+/// recognition, in which one digit may have been misread as a similar
+/// looking one.
+///
+/// This is [`fix_ocr_with`](fn.fix_ocr_with.html) using the default
+/// [`OCR_CONFUSIONS`](constant.OCR_CONFUSIONS.html) table.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// // The 6 in the fourth position was read as a 5
+/// assert_eq!(gtin13::fix_ocr("4005381333931").unwrap(), "4006381333931");
+/// assert_eq!(gtin13::fix_ocr("4006381333931").unwrap(), "4006381333931");
+/// ```
+pub fn fix_ocr(code: &str) -> Result<String, FixError> {
+    fix_ocr_with(code, OCR_CONFUSIONS)
+}
+
+/// Attempt to repair a GTIN-13 code in which one digit may have been
+/// misread as another, trying the substitutions in `confusions`.
+///
+/// A valid code is returned unchanged. For a 13-digit code that fails
+/// [`check`](fn.check.html), every single substitution of one digit by
+/// the other digit of a pair in `confusions` is tried, in both
+/// directions and at every position, including the check digit. If
+/// exactly one resulting code is valid, it is returned. If several
+/// different codes are valid, the intended one cannot be determined and
+/// `FixError::Ambiguous` is returned. If none is,
+/// `FixError::CheckDigitIncorrect` is returned with the check digits of
+/// the input as given.
+///
+/// This is distinct from
+/// [`repair_transposition`](fn.repair_transposition.html), which repairs
+/// keying mistakes. As there, no whitespace is stripped and no padding is
+/// performed, so the input must be exactly 13 digits.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// // A scanner that confuses 3 and 8
+/// let confusions = [('3', '8')];
+/// assert_eq!(
+///     gtin13::fix_ocr_with("0086000291452", &confusions).unwrap(),
+///     "0036000291452"
+/// );
+/// assert!(gtin13::fix_ocr("0086000291452").is_err());
+/// ```
+pub fn fix_ocr_with(code: &str, confusions: &[(char, char)]) -> Result<String, FixError> {
+    match validate(code) {
+        Ok(()) => return Ok(code.to_string()),
+        Err(FixError::CheckDigitIncorrect { .. }) => {}
+        Err(err) => return Err(err),
+    }
+
+    let mut repaired: Option<String> = None;
+    let mut bytes = code.as_bytes().to_vec();
+    for i in 0..13 {
+        let original = char::from(bytes[i]);
+        for &(a, b) in confusions {
+            let replacement = if original == a {
+                b
+            } else if original == b {
+                a
+            } else {
+                continue;
+            };
+            if !replacement.is_ascii_digit() {
+                continue;
+            }
+
+            bytes[i] = replacement as u8;
+            let candidate: String = bytes.iter().map(|&b| char::from(b)).collect();
+            bytes[i] = original as u8;
+
+            if check(&candidate) {
+                match repaired {
+                    Some(ref previous) if *previous != candidate => {
+                        return Err(FixError::Ambiguous);
+                    }
+                    _ => repaired = Some(candidate),
+                }
+            }
+        }
+    }
+
+    repaired.ok_or_else(|| check_digit_error(code))
+}
+
+/// Split a valid EAN-13 code into the parts encoded by the symbol: the
+/// first digit, the left group with its parity pattern, and the right
+/// group.
+///
+/// The parity pattern follows the standard EAN-13 table, so a first
+/// digit of `0` gives all-`L` parity, as in a UPC-A symbol. The code must
+/// be exactly 13 digits with a correct check digit; it is not trimmed or
+/// padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+/// use gtin_validate::gtin13::Parity::{G, L};
+///
+/// let structure = gtin13::structure("4006381333931").unwrap();
+/// assert_eq!(structure.first_digit, 4);
+/// assert_eq!(structure.left, [0, 0, 6, 3, 8, 1]);
+/// assert_eq!(structure.parity, [L, G, L, L, G, G]);
+/// assert_eq!(structure.right, [3, 3, 3, 9, 3, 1]);
+/// ```
+pub fn structure(code: &str) -> Result<Ean13Structure, FixError> {
+    validate(code)?;
+
+    let digits: Vec<u8> = code.bytes().map(|b| b - b'0').collect();
+    let mut left = [0; 6];
+    let mut right = [0; 6];
+    left.copy_from_slice(&digits[1..7]);
+    right.copy_from_slice(&digits[7..]);
+
+    Ok(Ean13Structure {
+        first_digit: digits[0],
+        left,
+        parity: PARITY_TABLE[usize::from(digits[0])],
+        right,
+    })
+}
+
+/// Format a valid EAN-13 code with the grouping printed beneath the
+/// symbol.
+///
+/// The first digit stands alone, followed by the six digits of the left
+/// half and the six digits of the right half, which ends with the check
+/// digit. The groups are separated by single spaces, as in
+/// `4 006381 333931`. The code must be exactly 13 digits with a correct
+/// check digit; it is not trimmed or padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin13;
+///
+/// assert_eq!(gtin13::format_grouped("4006381333931").unwrap(), "4 006381 333931");
+/// assert!(gtin13::format_grouped("4006381333932").is_err());
+/// ```
+pub fn format_grouped(code: &str) -> Result<String, FixError> {
+    structure(code)?;
+    Ok(format!("{} {} {}", &code[..1], &code[1..7], &code[7..]))
+}
+
+/// Check each code of a stream as it arrives, pairing it with the result
+/// of [`check`](fn.check.html). Available with the `futures` feature.
+///
+/// Validation is synchronous and happens when each item is polled, so
+/// the stream is never buffered and the order of the codes is preserved.
+///
+/// # Examples
+/// ```
+/// extern crate futures;
+/// extern crate gtin_validate;
+///
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use gtin_validate::gtin13;
+///
+/// # fn main() {
+/// let codes = stream::iter(vec!["4006381333931".to_string(), "123".to_string()]);
+/// let results: Vec<(String, bool)> = block_on(gtin13::check_stream(codes).collect());
+/// assert_eq!(
+///     results,
+///     vec![
+///         ("4006381333931".to_string(), true),
+///         ("123".to_string(), false),
+///     ]
+/// );
+/// # }
+/// ```
+#[cfg(feature = "futures")]
+pub fn check_stream<S: Stream<Item = String>>(s: S) -> impl Stream<Item = (String, bool)> {
+    s.map(|code| {
+        let valid = check(&code);
+        (code, valid)
+    })
+}
+
+// Validate a code that must be exactly a GTIN-13, without trimming or
+// padding, reporting why it is invalid.
+fn validate(code: &str) -> Result<(), FixError> {
+    if !code.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if code.len() > 13 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 13 {
+        return Err(FixError::TooShort);
+    }
+    if !utils::is_ascii_numeric(code) {
+        return Err(FixError::NonNumericString);
+    }
+    if !check(code) {
+        return Err(check_digit_error(code));
+    }
+
+    Ok(())
+}
+
+// Describe why a code of the full width failed `check`: either it is
+// not all digits, or its check digit is wrong.
+fn check_digit_error(code: &str) -> FixError {
+    if !utils::is_ascii_numeric(code) {
+        return FixError::NonNumericString;
+    }
+    let (expected, found) = utils::check_digit_pair(code.as_bytes());
+    FixError::CheckDigitIncorrect { expected, found }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use super::check;
+    use super::check_at;
+    use super::check_digit_diagnostic;
+    use super::check_digit_for_prefix;
+    use super::check_digits;
+    use super::check_with_addon;
+    use super::check_with_mapper;
+    use super::check_with_prefixes;
+    use super::classify;
+    use super::fix;
+    use super::fix_at;
+    use super::fix_counting;
+    use super::fix_lenient;
+    use super::fix_ocr;
+    use super::fix_ocr_with;
+    use super::fix_or_skip;
+    use super::fix_unicode;
+    use super::fix_was_modified;
+    use super::fix_with_input;
+    use super::format_grouped;
+    use super::pad_and_check;
+    use super::parse_accepting_upca;
+    use super::repair_transposition;
+    use super::strip_aim_identifier;
+    use super::structure;
+    use super::to_gtin12;
+    use super::validate_all;
+    use super::FixError;
+    use super::FixStats;
+    use super::Fixer;
+    use super::Gtin13;
+    use super::Parity::{G, L};
+    use super::Validity;
+    use super::MAX_INPUT_LEN;
+    use gtin::{GtinError, GtinKind};
+    use gtin12;
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn check_stream_preserves_order() {
+        use super::check_stream;
+        use futures::executor::block_on;
+        use futures::stream::{self, StreamExt};
+
+        let codes = ["1498279802125", "1498279802124", "", "0000000000000"];
+        let input = stream::iter(codes.iter().map(|code| code.to_string()));
+        let results: Vec<(String, bool)> = block_on(check_stream(input).collect());
+        assert_eq!(
+            results,
+            vec![
+                ("1498279802125".to_string(), true),
+                ("1498279802124".to_string(), false),
+                ("".to_string(), false),
+                ("0000000000000".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_valid() {
+        assert_eq!(check("0000000000000"), true);
+        assert_eq!(check("8845791354268"), true);
+        assert_eq!(check("0334873614126"), true);
+    }
+
+    #[test]
+    fn check_invalid_length() {
+        assert_eq!(check("000"), false);
+        assert_eq!(check("00000000000000"), false);
+    }
+
+    #[test]
+    fn check_non_ascii() {
+        assert_eq!(check("❤"), false);
+    }
+
+    #[test]
+    fn check_non_numeric() {
+        assert_eq!(check("a"), false);
+        assert_eq!(check("abcdabcdabcda"), false); // length 13
+        assert_eq!(check("000000000000a"), false); // invalid check digit
+    }
+
+    #[test]
+    fn check_invalid_check_digit() {
+        assert_eq!(check("0000000000001"), false);
+        assert_eq!(check("0000000000002"), false);
+        assert_eq!(check("0000000000003"), false);
+        assert_eq!(check("0000000000004"), false);
+        assert_eq!(check("0000000000005"), false);
+        assert_eq!(check("0000000000006"), false);
+        assert_eq!(check("0000000000007"), false);
+        assert_eq!(check("0000000000008"), false);
+        assert_eq!(check("0000000000009"), false);
+    }
+
+    #[test]
+    fn check_static_data() {
+        assert_eq!(check("0000000000000"), true);
+        assert_eq!(check("0123456789012"), true);
+        assert_eq!(check("0123456789013"), false);
+        assert_eq!(check("0999999999993"), true);
+        assert_eq!(check("0999999999999"), false);
+        assert_eq!(check("4459121265748"), true);
+        assert_eq!(check("4459121265747"), false);
+    }
+
+    #[test]
+    fn check_at_record() {
+        let record = "A1|4006381333931|1498279802125|EA";
+        assert_eq!(check_at(record, 3), true);
+        assert_eq!(check_at(record, 17), true);
+        assert_eq!(check_at(record, 2), false);
+        assert_eq!(check_at(record, 4), false);
+        assert_eq!(check_at(record, 20), false); // Field runs past the end
+        assert_eq!(check_at(record, usize::MAX), false);
+        assert_eq!(check_at("4006381333931", 0), true);
+        assert_eq!(check_at("❤4006381333931", 3), true);
+        assert_eq!(check_at("❤4006381333931", 1), false); // Not a char boundary
+    }
+
+    #[test]
+    fn fix_at_record() {
+        let record = "A1|  36000291452|4006381333931 |EA";
+        assert_eq!(fix_at(record, 3).unwrap(), "0036000291452");
+        assert_eq!(fix_at(record, 17).unwrap(), "4006381333931");
+        assert!(fix_at(record, 2).is_err()); // Misaligned
+        assert_eq!(fix_at(record, 30), Err(FixError::TooShort));
+        assert_eq!(fix_at(record, usize::MAX), Err(FixError::TooShort));
+        assert_eq!(fix_at("❤4006381333931", 1), Err(FixError::NonAsciiString));
+    }
+
+    fn full_width(c: char) -> Option<u8> {
+        match c {
+            '０'..='９' => Some((c as u32 - '０' as u32) as u8),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn check_with_mapper_full_width() {
+        assert_eq!(
+            check_with_mapper("１４９８２７９８０２１２５", full_width),
+            true
+        );
+        assert_eq!(
+            check_with_mapper("４４５９１２１２６５７４８", full_width),
+            true
+        );
+        assert_eq!(
+            check_with_mapper("１４９８２７９８０２１２４", full_width),
+            false
+        );
+        assert_eq!(
+            check_with_mapper("１４９８２７９８０２１２", full_width),
+            false
+        );
+        assert_eq!(
+            check_with_mapper("１４９８２７９８０２１２５０", full_width),
+            false
+        );
+        assert_eq!(check_with_mapper("1498279802125", full_width), false);
+        assert_eq!(check_with_mapper("", full_width), false);
+    }
+
+    #[test]
+    fn check_with_mapper_out_of_range() {
+        assert_eq!(check_with_mapper("1498279802125", |_| Some(10)), false);
+        assert_eq!(
+            check_with_mapper("1498279802125", |c| c.to_digit(10).map(|d| d as u8)),
+            true
+        );
+    }
+
+    #[test]
+    fn fix_non_ascii() {
+        assert!(fix("❤").is_err());
+    }
+
+    #[test]
+    fn fix_too_long() {
+        assert_eq!(
+            fix("00000000000000"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+    }
+
+    #[test]
+    fn fix_incorrect_check_digit() {
+        assert_eq!(
+            fix("8845791354262"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 8,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn fix_was_modified_clean() {
+        assert_eq!(
+            fix_was_modified("4006381333931"),
+            Ok(("4006381333931".to_string(), false))
+        );
+    }
+
+    #[test]
+    fn fix_was_modified_whitespace() {
+        assert_eq!(
+            fix_was_modified(" 4006381333931\r\n"),
+            Ok(("4006381333931".to_string(), true))
+        );
+    }
+
+    #[test]
+    fn fix_was_modified_padded() {
+        assert_eq!(
+            fix_was_modified("495205944325"),
+            Ok(("0495205944325".to_string(), true))
+        );
+        assert_eq!(
+            fix_was_modified("4006381333932"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn fixer_reuses_buffer() {
+        let mut fixer = Fixer::new();
+        assert_eq!(fixer.fix("4006381333931"), Ok("4006381333931"));
+        assert_eq!(fixer.fix(" 495205944325 "), Ok("0495205944325"));
+        assert_eq!(fixer.fix("❤"), Err(FixError::NonAsciiString));
+        assert_eq!(fixer.fix("abc"), Err(FixError::NonNumericString));
+        assert_eq!(
+            fixer.fix("40063813339310"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(fixer.fix("036000291452"), Ok("0036000291452"));
+        assert_eq!(fixer.buffer.capacity(), 13);
+    }
+
+    #[test]
+    fn check_digits_exact() {
+        let digits = [4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3, 1];
+        assert_eq!(check_digits(digits.iter().cloned()), true);
+        assert_eq!(check_digits([0; 13].iter().cloned()), true);
+        assert_eq!(
+            check_digits("1498279802125".bytes().map(|b| b - b'0')),
+            true
+        );
+        let wrong = [4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3, 2];
+        assert_eq!(check_digits(wrong.iter().cloned()), false);
+    }
+
+    #[test]
+    fn check_digits_wrong_count() {
+        let digits = [4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3, 1, 0];
+        assert_eq!(check_digits(digits[..12].iter().cloned()), false);
+        assert_eq!(check_digits(digits.iter().cloned()), false);
+        assert_eq!(check_digits(::std::iter::empty()), false);
+        assert_eq!(check_digits(::std::iter::repeat(0)), false);
+    }
+
+    #[test]
+    fn check_digits_out_of_range() {
+        let digits = [4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3, 11];
+        assert_eq!(check_digits(digits.iter().cloned()), false);
+        assert_eq!(check_digits("4006381333931".bytes()), false); // ASCII, not values
+    }
+
+    #[test]
+    fn check_with_prefixes_matching() {
+        let prefixes = ["4006381", "0036000"];
+        assert_eq!(check_with_prefixes("4006381333931", &prefixes), true);
+        assert_eq!(check_with_prefixes("0036000291452", &prefixes), true);
+        assert_eq!(check_with_prefixes("4006381333931", &["4"]), true);
+        assert_eq!(
+            check_with_prefixes("4006381333931", &["4006381333931"]),
+            true
+        );
+    }
+
+    #[test]
+    fn check_with_prefixes_not_matching() {
+        let prefixes = ["4006381", "0036000"];
+        assert_eq!(check_with_prefixes("1498279802125", &prefixes), false);
+        assert_eq!(check_with_prefixes("4006382333930", &prefixes), false);
+        assert_eq!(
+            check_with_prefixes("4006381333931", &["40063813339310"]),
+            false
+        );
+        // The code must still be valid
+        assert_eq!(check_with_prefixes("4006381333932", &prefixes), false);
+        assert_eq!(check_with_prefixes("4006381333932", &[]), false);
+        assert_eq!(check_with_prefixes("400638133393", &prefixes), false);
+    }
+
+    #[test]
+    fn check_with_prefixes_empty_allowlist() {
+        assert_eq!(check_with_prefixes("4006381333931", &[]), true);
+        assert_eq!(check_with_prefixes("1498279802125", &[]), true);
+    }
+
+    #[test]
+    fn classify_each_state() {
+        assert_eq!(classify("4006381333931"), Validity::Valid);
+        assert_eq!(classify("0036000291452"), Validity::Valid);
+        assert_eq!(classify("495205944325"), Validity::Repairable);
+        assert_eq!(classify(" 4006381333931\n"), Validity::Repairable);
+        assert_eq!(classify("4006381333932"), Validity::Invalid);
+        assert_eq!(classify("SKU-447100-XL"), Validity::Invalid);
+        assert_eq!(classify("❤"), Validity::Invalid);
+    }
+
+    #[test]
+    fn validate_all_valid() {
+        assert_eq!(validate_all("4006381333931"), vec![]);
+        assert_eq!(validate_all("0000000000000"), vec![]);
+    }
+
+    #[test]
+    fn validate_all_single_error() {
+        assert_eq!(validate_all("400638133393"), vec![FixError::TooShort]);
+        assert_eq!(
+            validate_all("400638133393A"),
+            vec![FixError::NonNumericString]
+        );
+        assert_eq!(
+            validate_all("4006381333932"),
+            vec![FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_all_several_errors() {
+        assert_eq!(
+            validate_all("40063813339310A"),
+            vec![
+                FixError::TooLong { trimmed_len: 15 },
+                FixError::NonNumericString
+            ]
+        );
+        assert_eq!(
+            validate_all("❤ 12"),
+            vec![
+                FixError::NonAsciiString,
+                FixError::TooShort,
+                FixError::NonNumericString
+            ]
+        );
+        // Digits only, but not ASCII
+        assert_eq!(
+            validate_all("４００６３８１３３３９３１"),
+            vec![
+                FixError::NonAsciiString,
+                FixError::TooLong { trimmed_len: 39 }
+            ]
+        );
+        assert_eq!(validate_all(""), vec![FixError::TooShort]);
+    }
+
+    #[test]
+    fn fix_unicode_separators() {
+        assert_eq!(
+            fix_unicode("400638\u{2012}1333931").unwrap(),
+            "4006381333931"
+        );
+        assert_eq!(
+            fix_unicode("4\u{2011}006381\u{2011}333931").unwrap(),
+            "4006381333931"
+        );
+        assert_eq!(
+            fix_unicode("4\u{2007}006381\u{2007}333931").unwrap(),
+            "4006381333931"
+        );
+        assert_eq!(
+            fix_unicode("\u{a0}036000\u{2013}29145\u{2010}2").unwrap(),
+            "0036000291452"
+        );
+        assert_eq!(
+            fix_unicode(" 40063813\u{202f}33931\u{2009}\n").unwrap(),
+            "4006381333931"
+        );
+    }
+
+    #[test]
+    fn fix_unicode_otherwise_strict() {
+        assert_eq!(fix_unicode("4006381333931").unwrap(), "4006381333931");
+        assert_eq!(
+            fix_unicode("400638-333931"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            fix_unicode("400638\u{2014}1333931"),
+            Err(FixError::NonAsciiString)
+        );
+        assert_eq!(
+            fix_unicode("４００６３８１３３３９３１"),
+            Err(FixError::NonAsciiString)
+        );
+        assert_eq!(
+            fix_unicode("400638\u{2012}1333932"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            fix_unicode(&"\u{2012}".repeat(MAX_INPUT_LEN)),
+            Err(FixError::TooLong {
+                trimmed_len: 3 * MAX_INPUT_LEN
+            })
+        );
+    }
+
+    #[test]
+    fn fix_lenient_apostrophe() {
+        assert_eq!(fix_lenient("'036000291452").unwrap(), "0036000291452");
+        assert_eq!(fix_lenient("'4006381333931").unwrap(), "4006381333931");
+        assert_eq!(fix_lenient(" '4006381333931\r\n").unwrap(), "4006381333931");
+        assert_eq!(fix_lenient("4006381333931").unwrap(), "4006381333931");
+        assert!(fix("'036000291452").is_err());
+        assert!(fix("'4006381333931").is_err());
+    }
+
+    #[test]
+    fn fix_lenient_invalid() {
+        assert!(fix_lenient("''036000291452").is_err());
+        assert!(fix_lenient("036000291452'").is_err());
+        assert!(fix_lenient("'036000291453").is_err());
+    }
+
+    #[test]
+    fn fix_needs_zero_padding() {
+        assert!(fix("0").is_ok());
+        assert_eq!(fix("0").unwrap(), "0000000000000");
+        assert_eq!(fix("123012301238").unwrap(), "0123012301238");
+    }
+
+    #[test]
+    fn pad_and_check_pads() {
+        assert_eq!(pad_and_check("495205944325").unwrap(), "0495205944325");
+        assert_eq!(pad_and_check("0").unwrap(), "0000000000000");
+        assert_eq!(pad_and_check("4006381333931").unwrap(), "4006381333931");
+    }
+
+    #[test]
+    fn pad_and_check_does_not_trim() {
+        assert_eq!(
+            pad_and_check(" 495205944325"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            pad_and_check("495205944325\n"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(fix(" 495205944325").unwrap(), "0495205944325");
+    }
+
+    #[test]
+    fn pad_and_check_invalid() {
+        assert_eq!(pad_and_check("❤"), Err(FixError::NonAsciiString));
+        assert_eq!(
+            pad_and_check("00000000000000"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(
+            pad_and_check("4952059-4325"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            pad_and_check("495205944326"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 5,
+                found: 6
+            })
+        );
+    }
+
+    #[test]
+    fn fix_error_from_other_modules() {
+        use gtin14;
+        use gtin8;
+        assert_eq!(
+            FixError::from(gtin8::FixError::NonAsciiString),
+            FixError::NonAsciiString
+        );
+        assert_eq!(
+            FixError::from(gtin8::FixError::TooLong { trimmed_len: 15 }),
+            FixError::TooLong { trimmed_len: 15 }
+        );
+        let err: FixError = gtin14::FixError::CheckDigitIncorrect {
+            expected: 1,
+            found: 2,
+        }
+        .into();
+        assert_eq!(
+            err,
+            FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn check_digit_for_prefix_complete() {
+        assert_eq!(check_digit_for_prefix("000000000000"), Some(0));
+        assert_eq!(check_digit_for_prefix("884579135426"), Some(8));
+        assert_eq!(check_digit_for_prefix("445912126574"), Some(8));
+    }
+
+    #[test]
+    fn check_digit_for_prefix_incomplete() {
+        assert_eq!(check_digit_for_prefix(""), None);
+        assert_eq!(check_digit_for_prefix("88457913542"), None);
+        assert_eq!(check_digit_for_prefix("8845791354268"), None);
+        assert_eq!(check_digit_for_prefix("88457913542a"), None);
+        assert_eq!(check_digit_for_prefix("❤❤❤❤"), None);
+    }
+
+    #[test]
+    fn to_gtin12_zero_prefixed() {
+        assert_eq!(to_gtin12("0036000291452").unwrap(), "036000291452");
+        assert_eq!(to_gtin12(" 0036000291452\n").unwrap(), "036000291452");
+        assert_eq!(to_gtin12("036000291452").unwrap(), "036000291452");
+        assert_eq!(to_gtin12("0000000000000").unwrap(), "000000000000");
+        assert!(gtin12::check(to_gtin12("0897854613315").unwrap()));
+    }
+
+    #[test]
+    fn to_gtin12_not_representable() {
+        assert_eq!(to_gtin12("4006381333931"), Err(FixError::NotRepresentable));
+        assert_eq!(to_gtin12("1498279802125"), Err(FixError::NotRepresentable));
+    }
+
+    #[test]
+    fn to_gtin12_invalid() {
+        assert_eq!(
+            to_gtin12("0036000291453"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            to_gtin12("00036000291452"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(to_gtin12("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn parse_accepting_upca_valid() {
+        assert_eq!(
+            parse_accepting_upca("036000291452").unwrap(),
+            "0036000291452"
+        );
+        assert_eq!(
+            parse_accepting_upca(" 036000291452\n").unwrap(),
+            "0036000291452"
+        );
+        assert_eq!(
+            parse_accepting_upca("0036000291452").unwrap(),
+            "0036000291452"
+        );
+        assert_eq!(
+            parse_accepting_upca("1498279802125").unwrap(),
+            "1498279802125"
+        );
+    }
+
+    #[test]
+    fn parse_accepting_upca_invalid() {
+        assert_eq!(
+            parse_accepting_upca("036000291453"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            parse_accepting_upca("1498279802124"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(parse_accepting_upca("36000291452"), Err(FixError::TooShort));
+        assert_eq!(
+            parse_accepting_upca("14982798021250"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(
+            parse_accepting_upca("03600029145X"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(parse_accepting_upca("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn fix_counting_outcomes() {
+        let stats = FixStats::new();
+        assert_eq!(
+            fix_counting("4006381333931", &stats).unwrap(),
+            "4006381333931"
+        );
+        assert_eq!(
+            fix_counting(" 4006381333931", &stats).unwrap(),
+            "4006381333931"
+        );
+        assert_eq!(
+            fix_counting("495205944325", &stats).unwrap(),
+            "0495205944325"
+        );
+        assert_eq!(
+            fix_counting("4006381333932", &stats),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(stats.already_valid(), 1);
+        assert_eq!(stats.repaired(), 2);
+        assert_eq!(stats.failed(), 1);
+    }
+
+    #[test]
+    fn fix_stats_shared_between_threads() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let stats = Arc::new(FixStats::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let stats = Arc::clone(&stats);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let _ = fix_counting("4006381333931", &stats);
+                        let _ = fix_counting("4006381333932", &stats);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(stats.already_valid(), 400);
+        assert_eq!(stats.repaired(), 0);
+        assert_eq!(stats.failed(), 400);
+    }
+
+    #[test]
+    fn check_digit_diagnostic_pairs() {
+        assert_eq!(check_digit_diagnostic("0000000000000"), Ok((0, 0)));
+        assert_eq!(check_digit_diagnostic("8845791354268"), Ok((8, 8)));
+        assert_eq!(check_digit_diagnostic("8845791354260"), Ok((8, 0)));
+        assert_eq!(check_digit_diagnostic("1498279802124"), Ok((5, 4)));
+    }
+
+    #[test]
+    fn check_digit_diagnostic_errors() {
+        assert_eq!(
+            check_digit_diagnostic("884579135426"),
+            Err(FixError::TooShort)
+        );
+        assert_eq!(
+            check_digit_diagnostic("88457913542680"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(
+            check_digit_diagnostic("884579135426X"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            check_digit_diagnostic(" 884579135426"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            check_digit_diagnostic("❤❤❤❤"),
+            Err(FixError::NonAsciiString)
+        );
+    }
+
+    #[test]
+    fn build_static_data() {
+        assert_eq!(build("4006381", "33393").unwrap(), "4006381333931");
+        assert_eq!(build("0614141", "00041").unwrap(), "0614141000418");
+        assert_eq!(build("", "884579135426").unwrap(), "8845791354268");
+    }
+
+    #[test]
+    fn build_invalid() {
+        assert_eq!(
+            build("4006381", "333930"),
+            Err(FixError::TooLong { trimmed_len: 13 })
+        );
+        assert_eq!(build("4006381", ""), Err(FixError::TooShort));
+        assert_eq!(build("400638 ", "33393"), Err(FixError::NonNumericString));
+        assert_eq!(build("40063❤", "33393"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn format_grouped_valid() {
+        assert_eq!(format_grouped("4006381333931").unwrap(), "4 006381 333931");
+        assert_eq!(format_grouped("0036000291452").unwrap(), "0 036000 291452");
+    }
+
+    #[test]
+    fn format_grouped_invalid() {
+        assert_eq!(
+            format_grouped("4006381333932"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(format_grouped("036000291452"), Err(FixError::TooShort));
+        assert_eq!(
+            format_grouped(" 4006381333931"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(format_grouped("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn structure_first_digit_encodings() {
+        let expected = [
+            "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG",
+            "LGLGGL", "LGGLGL",
+        ];
+        for (first, pattern) in expected.iter().enumerate() {
+            let mut body = format!("{}00000000000", first);
+            body.push(char::from(b'0' + check_digit_for_prefix(&body).unwrap()));
+            let parity: String = structure(&body)
+                .unwrap()
+                .parity
+                .iter()
+                .map(|p| match *p {
+                    L => 'L',
+                    G => 'G',
+                })
+                .collect();
+            assert_eq!(parity, *pattern);
+        }
+    }
+
+    #[test]
+    fn structure_groups() {
+        let parts = structure("0036000291452").unwrap();
+        assert_eq!(parts.first_digit, 0);
+        assert_eq!(parts.left, [0, 3, 6, 0, 0, 0]);
+        assert_eq!(parts.parity, [L; 6]);
+        assert_eq!(parts.right, [2, 9, 1, 4, 5, 2]);
+
+        let parts = structure("9780131103627").unwrap();
+        assert_eq!(parts.first_digit, 9);
+        assert_eq!(parts.parity, [L, G, G, L, G, L]);
+    }
+
+    #[test]
+    fn structure_invalid() {
+        assert_eq!(
+            structure("4006381333932"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(structure("400638133393"), Err(FixError::TooShort));
+        assert_eq!(
+            structure("40063813339310"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(structure("400638133393A"), Err(FixError::NonNumericString));
+        assert_eq!(structure("❤❤❤❤"), Err(FixError::NonAsciiString));
+        assert_eq!(structure("40063813339❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn check_with_addon_lengths() {
+        assert_eq!(check_with_addon("4006381333931"), true);
+        assert_eq!(check_with_addon("400638133393105"), true);
+        assert_eq!(check_with_addon("400638133393190000"), true);
+        assert_eq!(check_with_addon("036000291452"), false);
+        assert_eq!(check_with_addon("40063813339310"), false);
+        assert_eq!(check_with_addon("4006381333931123"), false);
+        assert_eq!(check_with_addon("40063813339311234"), false);
+        assert_eq!(check_with_addon("4006381333931123456"), false);
+        assert_eq!(check_with_addon(""), false);
+    }
+
+    #[test]
+    fn check_with_addon_invalid() {
+        // Bad check digit in the main code
+        assert_eq!(check_with_addon("400638133393205"), false);
+        assert_eq!(check_with_addon("400638133393290000"), false);
+        // Invalid supplement
+        assert_eq!(check_with_addon("40063813339310A"), false);
+        assert_eq!(check_with_addon("4006381333931 05"), false);
+        assert_eq!(check_with_addon("40063813339319000-"), false);
+        assert_eq!(check_with_addon("4006381333931❤"), false);
+        assert_eq!(check_with_addon("❤4006381333931"), false);
+    }
+
+    #[test]
+    fn strip_aim_identifier_recognized() {
+        assert_eq!(strip_aim_identifier("]E04006381333931"), "4006381333931");
+        assert_eq!(strip_aim_identifier("]E34006381333931"), "4006381333931");
+        assert_eq!(strip_aim_identifier("]E414567810"), "14567810");
+        assert_eq!(strip_aim_identifier("]E0"), "");
+    }
+
+    #[test]
+    fn strip_aim_identifier_unrecognized() {
+        assert_eq!(strip_aim_identifier("4006381333931"), "4006381333931");
+        assert_eq!(strip_aim_identifier("]E54006381333931"), "]E54006381333931");
+        assert_eq!(strip_aim_identifier("]C04006381333931"), "]C04006381333931");
+        assert_eq!(strip_aim_identifier("]E"), "]E");
+        assert_eq!(strip_aim_identifier(""), "");
+    }
+
+    #[test]
+    fn try_from_bytes_valid() {
+        let code = Gtin13::try_from(&b"1498279802125"[..]).unwrap();
+        assert_eq!(code.as_str(), "1498279802125");
+        assert_eq!(Gtin13::try_from("1498279802125"), Ok(code));
+    }
+
+    #[test]
+    fn digit_at_bounds() {
+        let code = Gtin13::try_from("4006381333931").unwrap();
+        let digits: Vec<u8> = (0..13).filter_map(|i| code.digit_at(i)).collect();
+        assert_eq!(digits, [4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3, 1]);
+        assert_eq!(code.digit_at(13), None);
+        assert_eq!(code.digit_at(usize::MAX), None);
+    }
+
+    #[test]
+    fn try_from_bytes_invalid() {
+        assert_eq!(
+            Gtin13::try_from(&b"\xff\xfe"[..]),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin13),
+            })
+        );
+        assert_eq!(
+            Gtin13::try_from("❤"),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin13),
+            })
+        );
+        assert_eq!(
+            Gtin13::try_from("14982798021250"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin13),
+                trimmed_len: 14,
+            })
+        );
+        assert_eq!(
+            Gtin13::try_from("498279802125"),
+            Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin13),
+            })
+        );
+        assert_eq!(
+            Gtin13::try_from(" 498279802125"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin13),
+            })
+        );
+        assert_eq!(
+            Gtin13::try_from("a498279802125"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin13),
+            })
+        );
+        assert_eq!(
+            Gtin13::try_from("1498279802126"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 5,
+                found: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn repair_transposition_unique() {
+        assert_eq!(
+            repair_transposition("6507704999626").unwrap(),
+            "6570704999626"
+        );
+        assert_eq!(
+            repair_transposition("3662585187122").unwrap(),
+            "3662585178122"
+        );
+    }
+
+    #[test]
+    fn repair_transposition_already_valid() {
+        assert_eq!(
+            repair_transposition("4006381333931").unwrap(),
+            "4006381333931"
+        );
+    }
+
+    #[test]
+    fn repair_transposition_ambiguous() {
+        assert_eq!(
+            repair_transposition("0406381333931"),
+            Err(FixError::Ambiguous)
+        );
+    }
+
+    #[test]
+    fn repair_transposition_invalid() {
+        assert_eq!(repair_transposition("❤"), Err(FixError::NonAsciiString));
+        assert_eq!(
+            repair_transposition("00000000000000"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(
+            repair_transposition("406381333931"),
+            Err(FixError::TooShort)
+        );
+        assert_eq!(
+            repair_transposition("40063813339a1"),
+            Err(FixError::NonNumericString)
+        );
+        // All digits equal apart from the check digit, so no swap helps
+        assert_eq!(
+            repair_transposition("0000000000001"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 0,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn fix_ocr_unique() {
+        assert_eq!(fix_ocr("4005381333931").unwrap(), "4006381333931");
+        assert_eq!(fix_ocr("1498279802725").unwrap(), "1498279802125");
+        assert_eq!(fix_ocr("4006381333931").unwrap(), "4006381333931");
+    }
+
+    #[test]
+    fn fix_ocr_ambiguous() {
+        // Either the 0 was an 8, or the final 7 was a 1
+        assert_eq!(fix_ocr("4006381333937"), Err(FixError::Ambiguous));
+    }
+
+    #[test]
+    fn fix_ocr_invalid() {
+        assert_eq!(
+            fix_ocr("4006381333981"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 6,
+                found: 1
+            })
+        );
+        assert_eq!(fix_ocr("40063813339a1"), Err(FixError::NonNumericString));
+        assert_eq!(
+            fix_ocr(" 4005381333931"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(fix_ocr("400538133393"), Err(FixError::TooShort));
+        assert_eq!(fix_ocr("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn fix_ocr_with_custom_table() {
+        assert_eq!(
+            fix_ocr_with("0086000291452", &[('3', '8')]).unwrap(),
+            "0036000291452"
+        );
+        assert_eq!(
+            fix_ocr_with("4005381333931", &[]),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 4,
+                found: 1
+            })
+        );
+        // Non-digit replacements are never tried
+        assert_eq!(
+            fix_ocr_with("4005381333931", &[('5', 'S')]),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 4,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn fix_max_input_len() {
+        let mut padded = " ".repeat(MAX_INPUT_LEN - 13);
+        padded.push_str("4006381333931");
+        assert!(fix(&padded).is_ok());
+        padded.push(' ');
+        assert_eq!(fix(&padded), Err(FixError::TooLong { trimmed_len: 13 }));
+        assert_eq!(
+            fix(" ".repeat(1 << 20)),
+            Err(FixError::TooLong { trimmed_len: 0 })
+        );
+    }
+
+    #[test]
+    fn fix_with_input_keeps_input() {
+        let kept = |input| {
+            if cfg!(feature = "error-input") {
+                Some(input)
+            } else {
+                None
+            }
+        };
+        assert_eq!(fix_with_input("4006381333931").unwrap(), "4006381333931");
+        let err = fix_with_input(" 4006381333932\n").unwrap_err();
+        assert_eq!(err.input(), kept("4006381333932"));
+        assert_eq!(
+            err.into_error(),
+            FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(fix_with_input("❤").unwrap_err().input(), kept("❤"));
+
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(fix_with_input(&long).unwrap_err().input(), None);
+
+        // The limit applies before trimming, even though the code fits
+        let padding = " ".repeat(30);
+        let padded = format!("{}4006381333932{}", padding, padding);
+        let err = fix_with_input(&padded).unwrap_err();
+        assert_eq!(err.input(), None);
+        assert_eq!(err.into_error(), FixError::TooLong { trimmed_len: 13 });
+    }
+
+    #[test]
+    fn fix_or_skip_static_data() {
+        assert_eq!(
+            fix_or_skip("4006381333931"),
+            Some("4006381333931".to_string())
+        );
+        assert_eq!(
+            fix_or_skip(" 495205944325\n"),
+            Some("0495205944325".to_string())
+        );
+        assert_eq!(fix_or_skip("14006381333931"), None);
+        assert_eq!(fix_or_skip("4006381333932"), None);
+        assert_eq!(fix_or_skip("❤"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn doesnt_crash(ref s in ".*") {
+            let _ = check(s);
+        }
+
+        #[test]
+        fn repair_transposition_result_is_valid(ref s in "[0-9]{13}") {
+            if let Ok(repaired) = repair_transposition(s) {
+                assert!(check(&repaired));
+            }
+        }
+
+        #[test]
+        fn fix_ocr_result_is_valid(ref s in "[0-9]{13}") {
+            if let Ok(repaired) = fix_ocr(s) {
+                assert!(check(&repaired));
+            }
+        }
+
+        #[test]
+        fn check_digits_matches_check(ref s in "[0-9]{11,15}") {
+            assert_eq!(check_digits(s.bytes().map(|b| b - b'0')), check(s));
+        }
+
+        #[test]
+        fn to_gtin12_round_trips(ref s in "0[0-9]{12}") {
+            if let Ok(upc) = to_gtin12(s) {
+                assert!(gtin12::check(&upc));
+                assert_eq!(parse_accepting_upca(&upc).unwrap(), *s);
+            }
+        }
+
+        #[test]
+        fn classify_matches_check_and_fix(ref s in ".*") {
+            let validity = classify(s);
+            assert_eq!(validity == Validity::Valid, check(s));
+            assert_eq!(validity == Validity::Invalid, fix(s).is_err());
+        }
+
+        #[test]
+        fn fix_unicode_matches_fix_for_ascii(ref s in "[ -~]*") {
+            assert_eq!(fix_unicode(s), fix(s));
+        }
+
+        #[test]
+        fn fixer_matches_fix(ref s in ".*") {
+            let mut fixer = Fixer::new();
+            assert_eq!(fixer.fix(s).map(str::to_string), fix(s));
+        }
+
+        #[test]
+        fn try_from_bytes_matches_check(ref bytes in prop::collection::vec(any::<u8>(), 0..20)) {
+            let valid = ::std::str::from_utf8(bytes).map(check).unwrap_or(false);
+            assert_eq!(Gtin13::try_from(&bytes[..]).is_ok(), valid);
+        }
+
+        #[test]
+        fn check_with_addon_doesnt_crash(ref s in ".*") {
+            let _ = check_with_addon(s);
+        }
+
+        #[test]
+        fn strip_aim_identifier_doesnt_crash(ref s in ".*") {
+            strip_aim_identifier(s);
+        }
+
+        #[test]
+        fn check_digit_for_prefix_completes_code(ref s in "[0-9]{12}") {
+            let digit = check_digit_for_prefix(s).unwrap();
+            assert!(check(format!("{}{}", s, digit)));
+        }
+
+        #[test]
+        fn check_digit_diagnostic_matches_check(ref s in "[0-9]{13}") {
+            let (computed, provided) = check_digit_diagnostic(s).unwrap();
+            assert_eq!(computed == provided, check(s));
+        }
+
+        #[test]
+        fn parse_accepting_upca_is_valid(ref s in "[0-9]{12,13}") {
+            if let Ok(code) = parse_accepting_upca(s) {
+                assert!(check(&code));
+            }
+        }
+
+        #[test]
+        fn build_is_valid(ref prefix in "[0-9]{6,10}") {
+            let product = &"000000"[..12 - prefix.len()];
+            let code = build(prefix, product).unwrap();
+            assert!(check(&code));
+            assert!(code.starts_with(prefix.as_str()));
+        }
+
+        #[test]
+        fn fix_doesnt_crash(ref bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+            if let Ok(fixed) = fix(String::from_utf8_lossy(bytes)) {
+                assert!(check(&fixed));
+            }
+        }
+
+        #[test]
+        fn fix_digits_doesnt_crash(ref s in "[0-9 \\t\\r\\n]{0,20}") {
+            if let Ok(fixed) = fix(s) {
+                assert!(check(&fixed));
+            }
         }
     }
 }
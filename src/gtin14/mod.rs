@@ -1,21 +1,170 @@
 //! Performs validation and correction of GTIN-14 codes.
 
+use std::array;
+use std::borrow::Cow;
+use std::ops::Range;
+
+#[cfg(feature = "arrayvec")]
+use arrayvec::ArrayString;
+
+use context::InputError;
+use gtin::{GtinError, GtinKind};
 use utils;
 
 /// Errors that make GTIN-14 correction impossible.
+///
+/// The `FixError` types of all GTIN modules share the same variants, so
+/// this type can be converted from any of them with `From`/`Into`. Each
+/// variant maps to the variant of the same name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FixError {
     /// The provided string contains non-ASCII characters.
     NonAsciiString,
+    /// The provided string contains characters other than ASCII digits.
+    NonNumericString,
     /// The provided code was too long to be valid.
-    TooLong,
+    TooLong {
+        /// The length of the code in bytes, after any whitespace
+        /// trimming done by the function returning the error. A length
+        /// within the expected width means that the input was rejected
+        /// only because of its surrounding whitespace.
+        trimmed_len: usize,
+    },
+    /// The provided code was too short to be valid.
+    TooShort,
     /// The calculated check-digit did not match the code's check-digit.
-    CheckDigitIncorrect,
+    CheckDigitIncorrect {
+        /// The check digit computed from the other digits of the code.
+        expected: u8,
+        /// The check digit the code ends with.
+        found: u8,
+    },
+    /// More than one correction of the code is possible, so none was
+    /// chosen.
+    Ambiguous,
+    /// The code is valid, but cannot be used for or converted into the
+    /// requested form.
+    NotRepresentable,
+}
+
+impl_fix_error_from!(gtin8, gtin12, gtin13);
+
+/// The longest input, in bytes, that [`fix`](fn.fix.html) will examine.
+///
+/// This is the 14-digit code plus an allowance for surrounding
+/// whitespace. Longer input is rejected with `TooLong` before it is
+/// copied, which bounds the work done on pathological input. The error
+/// still reports the trimmed length, so input that was only too long
+/// because of its whitespace can be told apart.
+pub const MAX_INPUT_LEN: usize = 14 + 32;
+
+gtin_newtype! {
+    /// A validated GTIN-14 code.
+    ///
+    /// Values can only be constructed from input that passes
+    /// [`check`](fn.check.html), so the wrapped string is always a valid
+    /// code. Construct them with `TryFrom`, from either a `&str` or the raw
+    /// bytes of the code.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::gtin14::Gtin14;
+    /// use std::convert::TryFrom;
+    ///
+    /// let code = Gtin14::try_from(&b"14567815983469"[..]).unwrap();
+    /// assert_eq!(code.as_str(), "14567815983469");
+    /// assert!(Gtin14::try_from("14567815983460").is_err()); // Bad check digit
+    /// ```
+    pub struct Gtin14(14);
+}
+
+impl Gtin14 {
+    /// Assemble a new GTIN-14 from its parts and compute the check digit.
+    ///
+    /// The indicator digit, the GS1 company prefix and the item reference
+    /// are concatenated and must together form exactly 13 digits. The
+    /// company prefix assigned by GS1 varies in length, and the item
+    /// reference fills the remaining digits.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::Gtin14;
+    ///
+    /// let gtin = Gtin14::from_parts('1', "0614141", "00041").unwrap();
+    /// assert_eq!(gtin.as_str(), "10614141000415");
+    ///
+    /// assert!(Gtin14::from_parts('1', "0614141", "0004").is_err()); // Too short
+    /// ```
+    pub fn from_parts(
+        indicator: char,
+        company_prefix: &str,
+        item_reference: &str,
+    ) -> Result<Gtin14, GtinError> {
+        let mut code = String::with_capacity(14);
+        code.push(indicator);
+        code.push_str(company_prefix);
+        code.push_str(item_reference);
+
+        if !code.is_ascii() {
+            return Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin14),
+            });
+        }
+        if !utils::is_ascii_numeric(&code) {
+            return Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14),
+            });
+        }
+        if code.len() > 13 {
+            return Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin14),
+                trimmed_len: code.len(),
+            });
+        }
+        if code.len() < 13 {
+            return Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin14),
+            });
+        }
+
+        // compute_check_digit skips the last byte, so add a placeholder
+        // and replace it with the computed digit.
+        code.push('0');
+        let check = utils::compute_check_digit(code.as_bytes());
+        code.pop();
+        code.push(char::from(b'0' + check));
+
+        Ok(Gtin14(code))
+    }
+}
+
+/// The logical structure of an ITF-14 (Interleaved 2 of 5) symbol, as
+/// returned by [`itf14_layout`](fn.itf14_layout.html).
+///
+/// This is the input for a renderer, not a rendering: it says which
+/// digits are encoded together, but not the widths of individual bars.
+///
+/// The bearer bars that protect the symbol from partial reads are not
+/// part of the layout, as they do not depend on the code. GS1 specifies
+/// a frame enclosing the whole symbol and its quiet zones for symbols
+/// printed directly on corrugated cartons, and bars across the top and
+/// bottom only where the printing method allows; the renderer chooses
+/// between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Itf14Layout {
+    /// The digits of the code in the pairs they are encoded in, from
+    /// left to right. The first digit of each pair is encoded by the five
+    /// bars of a character, and the second by the five spaces
+    /// interleaved with them.
+    pub pairs: [(u8, u8); 7],
 }
 
 /// Check that a GTIN-14 code is valid by confirming that it is exactly
 /// 14 digits in length and that the check-digit is correct.
 ///
+/// The code may be any string type, such as `&str`, `String` or
+/// `Cow<str>`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin14;
@@ -23,23 +172,32 @@ pub enum FixError {
 /// assert_eq!(gtin14::check("14567815983469"), true);  // Valid GTIN-14
 /// assert_eq!(gtin14::check("1456781598346"), false);  // too short
 /// assert_eq!(gtin14::check("14567815983468"), false); // Bad check digit
+/// assert_eq!(gtin14::check(String::from("14567815983469")), true);
 /// ```
-pub fn check(code: &str) -> bool {
-    if code.len() != 14 {
-        return false;
-    }
-    if !utils::is_ascii_numeric(code) {
-        return false;
-    }
+#[must_use = "the result of the check tells whether the code is valid"]
+pub fn check(code: impl AsRef<str>) -> bool {
+    fn inner(code: &str) -> bool {
+        if code.is_empty() {
+            return false;
+        }
+        if code.len() != 14 {
+            return false;
+        }
+        if !utils::is_ascii_numeric(code) {
+            return false;
+        }
 
-    // Calculate and compare check digit
-    let bytes = code.as_bytes();
-    let check = utils::compute_check_digit(bytes);
-    if check != bytes[13] - 48 {
-        return false;
+        // Calculate and compare check digit
+        let bytes = code.as_bytes();
+        let check = utils::compute_check_digit(bytes);
+        if check != bytes[13] - 48 {
+            return false;
+        }
+
+        true
     }
 
-    true
+    inner(code.as_ref())
 }
 
 /// Attempt to fix an invalid GTIN-14 code by stripping whitespace from
@@ -50,6 +208,10 @@ pub fn check(code: &str) -> bool {
 /// entry and software that treats GTINs as integers rather than
 /// strings, thus truncating the leading zeros.
 ///
+/// As with [`check`](fn.check.html), the code may be any string type.
+/// Input longer than [`MAX_INPUT_LEN`](constant.MAX_INPUT_LEN.html)
+/// bytes is rejected with `TooLong`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin14;
@@ -74,28 +236,685 @@ pub fn check(code: &str) -> bool {
 ///   Err(_) => {println!("Could not fix GTIN-14");}
 /// }
 /// ```
-pub fn fix(code: &str) -> Result<String, FixError> {
-    let mut fixed = code.trim().to_string();
+#[must_use = "the fixed code is returned rather than modified in place"]
+pub fn fix(code: impl AsRef<str>) -> Result<String, FixError> {
+    #[inline]
+    fn inner(code: &str) -> Result<String, FixError> {
+        if code.len() <= MAX_INPUT_LEN {
+            let trimmed = code.trim();
+            if trimmed.len() == 14 {
+                validate(trimmed)?;
+                return Ok(trimmed.to_string());
+            }
+        }
+
+        fix_padded(code)
+    }
+
+    inner(code.as_ref())
+}
 
-    if !fixed.is_ascii() {
+// Codes that need padding are the uncommon case, so keep this path out
+// of line to leave the full-length path in `fix` small.
+#[cold]
+#[inline(never)]
+fn fix_padded(code: &str) -> Result<String, FixError> {
+    let digits = fix_digits(code)?;
+    Ok(digits.iter().map(|&b| char::from(b)).collect())
+}
+
+// Trim, zero-pad and validate a code, returning its digits. The variants
+// of `fix` differ only in where they write the result, so they all call
+// this to behave identically; `fix` itself only skips it for full-length
+// codes, which `validate` checks the same way.
+#[inline]
+fn fix_digits(code: &str) -> Result<[u8; 14], FixError> {
+    if code.len() > MAX_INPUT_LEN {
+        return Err(FixError::TooLong {
+            trimmed_len: code.trim().len(),
+        });
+    }
+    let trimmed = code.trim();
+
+    if !trimmed.is_ascii() {
         return Err(FixError::NonAsciiString);
     }
-    if fixed.len() > 14 {
-        return Err(FixError::TooLong);
+    if trimmed.len() > 14 {
+        return Err(FixError::TooLong {
+            trimmed_len: trimmed.len(),
+        });
+    }
+
+    let mut digits = [b'0'; 14];
+    digits[14 - trimmed.len()..].copy_from_slice(trimmed.as_bytes());
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return Err(FixError::NonNumericString);
+    }
+    let (expected, found) = utils::check_digit_pair(&digits);
+    if expected != found {
+        return Err(FixError::CheckDigitIncorrect { expected, found });
+    }
+
+    Ok(digits)
+}
+
+/// Fix a GTIN-14 code as [`fix`](fn.fix.html) does, keeping a copy of the
+/// trimmed input in the error.
+///
+/// The copy is only made when `fix` fails, and only with the
+/// `error-input` feature. See [`InputError`](../struct.InputError.html).
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// let err = gtin14::fix_with_input("14567815983468 ").unwrap_err();
+/// #[cfg(feature = "error-input")]
+/// assert_eq!(err.input(), Some("14567815983468"));
+/// ```
+pub fn fix_with_input(code: &str) -> Result<String, InputError<FixError>> {
+    fix(code).map_err(|error| InputError::new(error, code, MAX_INPUT_LEN))
+}
+
+/// Fix a GTIN-14 code as [`fix`](fn.fix.html) does, also accepting a
+/// code with one leading zero too many.
+///
+/// Spreadsheets that pad codes to a fixed width sometimes add one zero
+/// too many, turning a GTIN-14 into 15 characters (as in
+/// `004527819983417`). After stripping whitespace from the left and
+/// right sides, a 15-character code starting with `0` has that zero
+/// removed and the rest is fixed as usual. Any other code is passed to
+/// `fix` unchanged, so a 15-character code starting with another
+/// character is still `TooLong`. The strict `fix` rejects all
+/// 15-character codes.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::fix_lenient("004527819983417").unwrap(), "04527819983417");
+/// assert_eq!(gtin14::fix_lenient("14567815983469").unwrap(), "14567815983469");
+/// assert!(gtin14::fix("004527819983417").is_err());
+/// assert!(gtin14::fix_lenient("104527819983417").is_err());
+/// ```
+#[must_use = "the fixed code is returned rather than modified in place"]
+pub fn fix_lenient(code: &str) -> Result<String, FixError> {
+    let trimmed = code.trim();
+    match trimmed.strip_prefix('0') {
+        Some(rest) if trimmed.len() == 15 => fix(rest),
+        _ => fix(trimmed),
+    }
+}
+
+/// Fix a GTIN-14 code as [`fix`](fn.fix.html) does, writing the result
+/// into a stack-allocated `ArrayString` rather than a heap `String`.
+///
+/// This gives the same result as `fix` without needing an allocator.
+/// Requires the `arrayvec` feature.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::fix_arrayvec(" 14567815983469").unwrap().as_str(), "14567815983469");
+/// assert_eq!(gtin14::fix_arrayvec("036000291452").unwrap().as_str(), "00036000291452");
+/// assert!(gtin14::fix_arrayvec("14567815983468").is_err());
+/// ```
+#[cfg(feature = "arrayvec")]
+pub fn fix_arrayvec(code: &str) -> Result<ArrayString<14>, FixError> {
+    let digits = fix_digits(code)?;
+
+    let mut fixed = ArrayString::new();
+    for &b in &digits {
+        fixed.push(char::from(b));
+    }
+    Ok(fixed)
+}
+
+/// Return a GTIN-14 code unchanged if it is already valid, and
+/// otherwise attempt to [`fix`](fn.fix.html) it.
+///
+/// Already-valid input is borrowed rather than copied, so data that is
+/// usually clean can be passed through without an allocation per code.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+/// use std::borrow::Cow;
+///
+/// assert!(matches!(gtin14::ensure("14567815983469"), Ok(Cow::Borrowed(_))));
+/// assert_eq!(gtin14::ensure("036000291452").unwrap(), "00036000291452");
+/// assert!(gtin14::ensure("14567815983468").is_err());
+/// ```
+pub fn ensure(code: &str) -> Result<Cow<'_, str>, FixError> {
+    if check(code) {
+        return Ok(Cow::Borrowed(code));
     }
-    fixed = utils::zero_pad(fixed, 14);
-    if !check(&fixed) {
-        return Err(FixError::CheckDigitIncorrect);
+
+    fix(code).map(Cow::Owned)
+}
+
+/// Replace the check digit of a 14-digit code with the one computed from
+/// its first 13 digits.
+///
+/// Unlike [`fix`](fn.fix.html), a wrong check digit is not an error: it
+/// is overwritten. This trusts the body of the code completely, so a
+/// mistake anywhere in the first 13 digits silently produces a valid
+/// code for a different product. Only use it when the first 13 digits
+/// are the source of truth, such as when the check digit was dropped or
+/// mangled by a system that stored the body correctly.
+///
+/// The code must be exactly 14 ASCII digits; it is not trimmed or
+/// padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::recompute_check_digit("14567815983460").unwrap(), "14567815983469");
+/// assert_eq!(gtin14::recompute_check_digit("14567815983469").unwrap(), "14567815983469");
+/// assert!(gtin14::fix("14567815983460").is_err());
+/// ```
+pub fn recompute_check_digit(code: &str) -> Result<String, FixError> {
+    match validate(code) {
+        Ok(()) | Err(FixError::CheckDigitIncorrect { .. }) => {}
+        Err(err) => return Err(err),
     }
 
+    let check = utils::compute_check_digit(code.as_bytes());
+    let mut fixed = String::with_capacity(14);
+    fixed.push_str(&code[..13]);
+    fixed.push(char::from(b'0' + check));
     Ok(fixed)
 }
 
+/// Attempt to fix a GTIN-14 read by a case scanner from an ITF-14
+/// symbol.
+///
+/// Raw ITF-14 scanner output may start with a three-character AIM
+/// symbology identifier for Interleaved 2 of 5 before the 14 digits:
+///
+/// * `]I0`: no check character verified by the scanner
+/// * `]I1`: check character verified and transmitted
+/// * `]I3`: check character verified but not transmitted
+///
+/// After stripping surrounding whitespace, at most one such identifier is
+/// removed from the start of the input. After `]I0` or `]I1` (or no
+/// identifier), the rest is passed to [`fix`](fn.fix.html). Nothing else
+/// is removed, so `fix` itself stays strict.
+///
+/// After `]I3` the scanner has already verified the check digit and
+/// dropped it, so exactly 13 digits must follow; the check digit is
+/// computed and appended to them.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::fix_scanned("]I114567815983469\r\n").unwrap(), "14567815983469");
+/// assert_eq!(gtin14::fix_scanned("]I31456781598346").unwrap(), "14567815983469");
+/// assert_eq!(gtin14::fix_scanned("14567815983469").unwrap(), "14567815983469");
+/// assert!(gtin14::fix("]I114567815983469").is_err());
+/// ```
+pub fn fix_scanned(raw: &str) -> Result<String, FixError> {
+    let raw = raw.trim();
+    let bytes = raw.as_bytes();
+    if bytes.len() < 3 || bytes[0] != b']' || bytes[1] != b'I' {
+        return fix(raw);
+    }
+
+    match bytes[2] {
+        b'0' | b'1' => fix(&raw[3..]),
+        b'3' => append_check_digit(&raw[3..]),
+        _ => fix(raw),
+    }
+}
+
+// Append the check digit to the 13 digits sent after `]I3`.
+fn append_check_digit(body: &str) -> Result<String, FixError> {
+    if !body.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if body.len() > 13 {
+        return Err(FixError::TooLong {
+            trimmed_len: body.len(),
+        });
+    }
+    if body.len() < 13 {
+        return Err(FixError::TooShort);
+    }
+    if !body.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(FixError::NonNumericString);
+    }
+
+    let mut fixed = String::with_capacity(14);
+    fixed.push_str(body);
+    // compute_check_digit ignores the final byte, so reserve its place
+    fixed.push('0');
+    let check = utils::compute_check_digit(fixed.as_bytes());
+    fixed.pop();
+    fixed.push(char::from(b'0' + check));
+    Ok(fixed)
+}
+
+/// Extract the GTIN-14 from a GS1 element string carrying the `01`
+/// (GTIN) application identifier, such as `(01)09506000134352`.
+///
+/// The application identifier may be written in parentheses, as in the
+/// human-readable form, or as the bare digits `01` directly in front of
+/// the 14-digit GTIN (16 digits in total). Input without any identifier
+/// is accepted as a plain GTIN-14. The extracted code is returned only if
+/// it is a valid GTIN-14.
+///
+/// Only the `01` application identifier is handled. Element strings
+/// holding other identifiers, or several concatenated elements, are
+/// rejected.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::extract_ai01("(01)09506000134352"), Some("09506000134352"));
+/// assert_eq!(gtin14::extract_ai01("0109506000134352"), Some("09506000134352"));
+/// assert_eq!(gtin14::extract_ai01("09506000134352"), Some("09506000134352"));
+/// assert_eq!(gtin14::extract_ai01("(02)09506000134352"), None);
+/// ```
+pub fn extract_ai01(element: &str) -> Option<&str> {
+    let code = if let Some(code) = element.strip_prefix("(01)") {
+        code
+    } else if element.len() == 16 && element.starts_with("01") {
+        &element[2..]
+    } else {
+        element
+    };
+
+    if check(code) {
+        Some(code)
+    } else {
+        None
+    }
+}
+
+/// Check that a GS1 element string holds a valid GTIN-14 under the `01`
+/// application identifier.
+///
+/// See [`extract_ai01`](fn.extract_ai01.html) for the accepted forms.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::check_ai01("(01)09506000134352"), true);
+/// assert_eq!(gtin14::check_ai01("(01)09506000134353"), false); // Bad check digit
+/// ```
+pub fn check_ai01(element: &str) -> bool {
+    extract_ai01(element).is_some()
+}
+
+/// Check that a string starts with a valid GTIN-14, ignoring anything
+/// after the first 14 characters.
+///
+/// This is deliberately lenient, for extracting a GTIN embedded at the
+/// start of a longer identifier, such as an asset code with a serial
+/// number appended. The first 14 characters must be ASCII digits forming
+/// a valid GTIN-14; the rest is not inspected at all. Use
+/// [`check`](fn.check.html) to require exactly 14 digits.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::check_prefix14("14567815983469SN-0042"), true);
+/// assert_eq!(gtin14::check_prefix14("14567815983469"), true);
+/// assert_eq!(gtin14::check_prefix14("1456781598346"), false); // Too short
+/// ```
+pub fn check_prefix14(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    if bytes.len() < 14 || !bytes[..14].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    check(&code[..14])
+}
+
+/// Check whether two GTIN-14 codes belong to the same packaging
+/// hierarchy by comparing their shared 12-digit core.
+///
+/// A GTIN-14 is made of an indicator digit (position 1), a 12-digit
+/// core holding the company prefix and item reference (positions 2 to
+/// 13), and a check digit (position 14). Case and pallet codes use a
+/// different indicator, and therefore a different check digit, than the
+/// base item they contain, but keep the same core. Only positions 2 to
+/// 13 are compared.
+///
+/// Both codes are first corrected with [`fix`](fn.fix.html), so a
+/// GTIN-13 or GTIN-12 of the contained item may be passed as well. An
+/// error is returned if either code cannot be fixed.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// // A case of the EAN-13 item 4006381333931 (indicator 1)
+/// assert_eq!(gtin14::shares_base("14006381333938", "4006381333931"), Ok(true));
+/// assert_eq!(gtin14::shares_base("14006381333938", "14567815983469"), Ok(false));
+/// assert!(gtin14::shares_base("14006381333938", "4006381333932").is_err());
+/// ```
+pub fn shares_base(a: &str, b: &str) -> Result<bool, FixError> {
+    let a = fix(a)?;
+    let b = fix(b)?;
+
+    Ok(a[1..13] == b[1..13])
+}
+
+/// Generate the GTIN-14 codes for every packaging level of a product.
+///
+/// Packaging levels (such as inner packs, cases and pallets) share the
+/// 12-digit core of the base item's GTIN-14 and differ in the indicator
+/// digit, which ranges from 0 (the base item itself) to 8. The check
+/// digit is recomputed for each level. Indicator 9 is reserved for
+/// variable measure items and is not generated.
+///
+/// The base code is corrected with [`fix`](fn.fix.html) first, so the
+/// GTIN-13 or GTIN-12 of the base item may be passed as well. It must
+/// have indicator 0, otherwise `FixError::NotRepresentable` is returned.
+/// Element `i` of the result holds the code with indicator `i`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// let levels = gtin14::all_packaging_levels("04006381333931").unwrap();
+/// assert_eq!(levels[0], "04006381333931");
+/// assert_eq!(levels[1], "14006381333938");
+/// assert_eq!(levels[8], "84006381333937");
+///
+/// assert!(gtin14::all_packaging_levels("14006381333938").is_err()); // Not indicator 0
+/// ```
+pub fn all_packaging_levels(base_gtin14: &str) -> Result<[String; 9], FixError> {
+    let base = fix(base_gtin14)?;
+    if !base.starts_with('0') {
+        return Err(FixError::NotRepresentable);
+    }
+
+    Ok(array::from_fn(|indicator| {
+        let mut bytes = [b'0'; 14];
+        bytes[0] = b'0' + indicator as u8;
+        bytes[1..13].copy_from_slice(&base.as_bytes()[1..13]);
+        bytes[13] = b'0' + utils::compute_check_digit(&bytes);
+        bytes.iter().map(|&b| char::from(b)).collect()
+    }))
+}
+
+/// Return the part of a GTIN-14 that identifies the product, leaving out
+/// the variable field of a variable measure code.
+///
+/// Codes with indicator 9 are variable measure items, and some internal
+/// conventions encode a weight, price or count in part of their digits.
+/// Two weighings of the same item then have different codes. The
+/// `variable` layout gives the positions of that field as a range of
+/// 0-based indices into the 14-digit code; for example `8..13` for a
+/// five-digit field just before the check digit. For indicator-9 codes
+/// only the stable digits are returned: those outside the field, without
+/// the check digit, which depends on the field. The result is the same
+/// for every measure of the item, and is not a GTIN: it is always
+/// shorter than 14 digits, so it cannot be mistaken for, or collide
+/// with, the result for a code with another indicator. Such codes are
+/// returned whole, as all their digits are part of the identifier.
+///
+/// The code is corrected with [`fix`](fn.fix.html) first, so an error is
+/// returned if it cannot be fixed. The layout must not cover the
+/// indicator digit or the check digit, otherwise
+/// `FixError::NotRepresentable` is returned.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// // The same item weighed twice
+/// assert_eq!(gtin14::stable_identifier("94006381012341", 8..13).unwrap(), "94006381");
+/// assert_eq!(gtin14::stable_identifier("94006381056789", 8..13).unwrap(), "94006381");
+///
+/// // Not a variable measure code
+/// assert_eq!(gtin14::stable_identifier("14006381333938", 8..13).unwrap(), "14006381333938");
+/// ```
+pub fn stable_identifier(code: &str, variable: Range<usize>) -> Result<String, FixError> {
+    if variable.start < 1 || variable.end > 13 || variable.start > variable.end {
+        return Err(FixError::NotRepresentable);
+    }
+    let code = fix(code)?;
+    if !code.starts_with('9') {
+        return Ok(code);
+    }
+
+    let mut stable = String::with_capacity(13 - variable.len());
+    stable.push_str(&code[..variable.start]);
+    stable.push_str(&code[variable.end..13]);
+    Ok(stable)
+}
+
+/// Heuristically detect GTIN-14 codes that are likely placeholder or
+/// test data rather than real product codes.
+///
+/// Returns true for 14-digit codes in which every digit is the same
+/// (such as `00000000000000` or `11111111111111`), and for codes whose
+/// first 13 digits count upwards, wrapping from 9 to 0 (such as
+/// `12345678901231`). The check digit is not validated, so this can be
+/// combined with [`check`](fn.check.html) as needed.
+///
+/// This is advisory only. Such codes may still be valid, and the caller
+/// decides whether to flag, reject or keep them.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::looks_like_placeholder("00000000000000"), true);
+/// assert_eq!(gtin14::looks_like_placeholder("12345678901231"), true);
+/// assert_eq!(gtin14::looks_like_placeholder("14567815983469"), false);
+/// ```
+pub fn looks_like_placeholder(code: &str) -> bool {
+    if code.len() != 14 || !utils::is_ascii_numeric(code) {
+        return false;
+    }
+
+    let bytes = code.as_bytes();
+    let all_same = bytes.iter().all(|&b| b == bytes[0]);
+    let ascending = bytes[..13]
+        .windows(2)
+        .all(|pair| (pair[0] - b'0' + 1) % 10 == pair[1] - b'0');
+
+    all_same || ascending
+}
+
+/// Describe the ITF-14 symbol for a valid GTIN-14: the pairs its digits
+/// are encoded in.
+///
+/// Interleaved 2 of 5 encodes digits in pairs, so the 14 digits of the
+/// code, including the check digit, form 7 pairs. The bearer bars are
+/// left to the renderer; see [`Itf14Layout`](struct.Itf14Layout.html).
+///
+/// The code must be exactly 14 digits with a correct check digit; it is
+/// not trimmed or padded.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// let layout = gtin14::itf14_layout("14567815983469").unwrap();
+/// assert_eq!(layout.pairs[0], (1, 4));
+/// assert_eq!(layout.pairs[6], (6, 9));
+/// ```
+pub fn itf14_layout(code: &str) -> Result<Itf14Layout, FixError> {
+    validate(code)?;
+
+    let bytes = code.as_bytes();
+    let mut pairs = [(0, 0); 7];
+    for (pair, digits) in pairs.iter_mut().zip(bytes.chunks(2)) {
+        *pair = (digits[0] - b'0', digits[1] - b'0');
+    }
+
+    Ok(Itf14Layout { pairs })
+}
+
+/// Check whether a valid GTIN-14 could be a zero-padded GTIN-8, which is
+/// the case when it starts with six zeros.
+///
+/// The classification is structural, not authoritative: it only says
+/// which widths the digits fit, not how the code was actually assigned
+/// or sourced. A padded GTIN-8 also fits the GTIN-12 and GTIN-13 widths,
+/// so the classifiers overlap; use [`shortest_kind`](fn.shortest_kind.html)
+/// for the narrowest one.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::is_gtin8_based("00000014567810"), true);
+/// assert_eq!(gtin14::is_gtin8_based("00036000291452"), false);
+/// ```
+pub fn is_gtin8_based(code: &str) -> bool {
+    check(code) && code.starts_with("000000")
+}
+
+/// Check whether a valid GTIN-14 could be a zero-padded GTIN-12 (UPC-A),
+/// which is the case when it starts with two zeros.
+///
+/// As with [`is_gtin8_based`](fn.is_gtin8_based.html), the classification
+/// is structural, not authoritative.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::is_gtin12_based("00036000291452"), true);
+/// assert_eq!(gtin14::is_gtin12_based("04006381333931"), false);
+/// ```
+pub fn is_gtin12_based(code: &str) -> bool {
+    check(code) && code.starts_with("00")
+}
+
+/// Check whether a valid GTIN-14 could be a zero-padded GTIN-13, which
+/// is the case when it starts with a zero.
+///
+/// As with [`is_gtin8_based`](fn.is_gtin8_based.html), the classification
+/// is structural, not authoritative.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin14;
+///
+/// assert_eq!(gtin14::is_gtin13_based("04006381333931"), true);
+/// assert_eq!(gtin14::is_gtin13_based("14006381333938"), false);
+/// ```
+pub fn is_gtin13_based(code: &str) -> bool {
+    check(code) && code.starts_with('0')
+}
+
+/// Return the narrowest width a valid GTIN-14 could have been padded
+/// from, judged by its leading zeros, or `None` if the code is not a
+/// valid GTIN-14.
+///
+/// This is the kind of the shortest code the GTIN-14 can be written as,
+/// and like the `is_gtin*_based` classifiers it is structural, not
+/// authoritative: a UPC-A whose company prefix starts with zeros, for
+/// example, is reported as GTIN-8 based.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{gtin14, GtinKind};
+///
+/// assert_eq!(gtin14::shortest_kind("00000014567810"), Some(GtinKind::Gtin8));
+/// assert_eq!(gtin14::shortest_kind("00036000291452"), Some(GtinKind::Gtin12));
+/// assert_eq!(gtin14::shortest_kind("04006381333931"), Some(GtinKind::Gtin13));
+/// assert_eq!(gtin14::shortest_kind("14006381333938"), Some(GtinKind::Gtin14));
+/// assert_eq!(gtin14::shortest_kind("14006381333939"), None);
+/// ```
+pub fn shortest_kind(code: &str) -> Option<GtinKind> {
+    if !check(code) {
+        return None;
+    }
+
+    Some(narrowest_kind(code))
+}
+
+// Return the narrowest width a GTIN-14 could have been padded from,
+// judged by its leading zeros alone, for a code already known to be
+// valid.
+pub(crate) fn narrowest_kind(code: &str) -> GtinKind {
+    if code.starts_with("000000") {
+        GtinKind::Gtin8
+    } else if code.starts_with("00") {
+        GtinKind::Gtin12
+    } else if code.starts_with('0') {
+        GtinKind::Gtin13
+    } else {
+        GtinKind::Gtin14
+    }
+}
+
+// Validate a code that must be exactly a GTIN-14, without trimming or
+// padding, reporting why it is invalid.
+fn validate(code: &str) -> Result<(), FixError> {
+    if !code.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if code.len() > 14 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 14 {
+        return Err(FixError::TooShort);
+    }
+    if !utils::is_ascii_numeric(code) {
+        return Err(FixError::NonNumericString);
+    }
+    if !check(code) {
+        return Err(check_digit_error(code));
+    }
+
+    Ok(())
+}
+
+// Describe why a code of the full width failed `check`: either it is
+// not all digits, or its check digit is wrong.
+fn check_digit_error(code: &str) -> FixError {
+    if !utils::is_ascii_numeric(code) {
+        return FixError::NonNumericString;
+    }
+    let (expected, found) = utils::check_digit_pair(code.as_bytes());
+    FixError::CheckDigitIncorrect { expected, found }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::all_packaging_levels;
     use super::check;
+    use super::check_ai01;
+    use super::check_prefix14;
+    use super::ensure;
+    use super::extract_ai01;
     use super::fix;
+    use super::fix_lenient;
+    use super::fix_scanned;
+    use super::fix_with_input;
+    use super::is_gtin12_based;
+    use super::is_gtin13_based;
+    use super::is_gtin8_based;
+    use super::itf14_layout;
+    use super::looks_like_placeholder;
+    use super::recompute_check_digit;
+    use super::shares_base;
+    use super::shortest_kind;
+    use super::stable_identifier;
     use super::FixError;
+    use super::Gtin14;
+    use super::MAX_INPUT_LEN;
+    use gtin::{GtinError, GtinKind};
+    use std::borrow::Cow;
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
 
     #[test]
     fn check_valid() {
@@ -149,12 +968,21 @@ mod tests {
 
     #[test]
     fn fix_too_long() {
-        assert_eq!(fix("000000000000000"), Err(FixError::TooLong));
+        assert_eq!(
+            fix("000000000000000"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
     }
 
     #[test]
     fn fix_incorrect_check_digit() {
-        assert_eq!(fix("17342894127889"), Err(FixError::CheckDigitIncorrect));
+        assert_eq!(
+            fix("17342894127889"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 4,
+                found: 9
+            })
+        );
     }
 
     #[test]
@@ -164,10 +992,657 @@ mod tests {
         assert_eq!(fix("8987561651112").unwrap(), "08987561651112");
     }
 
+    #[test]
+    fn fix_error_from_other_modules() {
+        use gtin13;
+        use gtin8;
+        assert_eq!(
+            FixError::from(gtin8::FixError::NonAsciiString),
+            FixError::NonAsciiString
+        );
+        assert_eq!(
+            FixError::from(gtin8::FixError::TooLong { trimmed_len: 15 }),
+            FixError::TooLong { trimmed_len: 15 }
+        );
+        let err: FixError = gtin13::FixError::CheckDigitIncorrect {
+            expected: 1,
+            found: 2,
+        }
+        .into();
+        assert_eq!(
+            err,
+            FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn extract_ai01_prefixes() {
+        assert_eq!(extract_ai01("(01)14567815983469"), Some("14567815983469"));
+        assert_eq!(extract_ai01("0114567815983469"), Some("14567815983469"));
+        assert_eq!(extract_ai01("14567815983469"), Some("14567815983469"));
+    }
+
+    #[test]
+    fn extract_ai01_invalid() {
+        assert_eq!(extract_ai01(""), None);
+        assert_eq!(extract_ai01("(01)"), None);
+        assert_eq!(extract_ai01("(01)1456781598346"), None); // too short
+        assert_eq!(extract_ai01("(01)14567815983468"), None); // bad check digit
+        assert_eq!(extract_ai01("(10)14567815983469"), None); // other AI
+        assert_eq!(extract_ai01("1014567815983469"), None); // other AI
+    }
+
+    #[test]
+    fn check_ai01_static_data() {
+        assert_eq!(check_ai01("(01)09506000134352"), true);
+        assert_eq!(check_ai01("0109506000134352"), true);
+        assert_eq!(check_ai01("(01)09506000134353"), false);
+    }
+
+    #[test]
+    fn shares_base_same_core() {
+        assert_eq!(shares_base("14006381333938", "24006381333935"), Ok(true));
+        assert_eq!(shares_base("14006381333938", "04006381333931"), Ok(true));
+        assert_eq!(shares_base(" 14006381333938", "4006381333931"), Ok(true));
+        assert_eq!(shares_base("14006381333938", "14006381333938"), Ok(true));
+    }
+
+    #[test]
+    fn shares_base_different_core() {
+        assert_eq!(shares_base("14006381333938", "14006381333945"), Ok(false));
+        assert_eq!(shares_base("14006381333938", "00000000000000"), Ok(false));
+    }
+
+    #[test]
+    fn shares_base_invalid() {
+        assert_eq!(
+            shares_base("14006381333939", "4006381333931"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 8,
+                found: 9
+            })
+        );
+        assert_eq!(
+            shares_base("14006381333938", "000000000000000"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_valid() {
+        let code = Gtin14::try_from(&b"14567815983469"[..]).unwrap();
+        assert_eq!(code.as_str(), "14567815983469");
+        assert_eq!(Gtin14::try_from("14567815983469"), Ok(code));
+    }
+
+    #[test]
+    fn try_from_bytes_invalid() {
+        assert_eq!(
+            Gtin14::try_from(&b"\xff\xfe"[..]),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::try_from("❤"),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::try_from("145678159834690"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin14),
+                trimmed_len: 15,
+            })
+        );
+        assert_eq!(
+            Gtin14::try_from("4567815983469"),
+            Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::try_from(" 4567815983469"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::try_from("a4567815983469"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::try_from("14567815983460"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin14),
+                expected: 9,
+                found: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn all_packaging_levels_static_data() {
+        let levels = all_packaging_levels("04006381333931").unwrap();
+        for (indicator, code) in levels.iter().enumerate() {
+            assert!(check(code));
+            assert_eq!(code[..1], indicator.to_string());
+            assert_eq!(code[1..13], *"400638133393");
+        }
+        assert_eq!(levels[2], "24006381333935");
+    }
+
+    #[test]
+    fn all_packaging_levels_fixes_input() {
+        let levels = all_packaging_levels(" 4006381333931").unwrap();
+        assert_eq!(levels[0], "04006381333931");
+    }
+
+    #[test]
+    fn all_packaging_levels_invalid() {
+        assert_eq!(
+            all_packaging_levels("14006381333938"),
+            Err(FixError::NotRepresentable)
+        );
+        assert_eq!(
+            all_packaging_levels("04006381333932"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn stable_identifier_masks_variable_field() {
+        assert_eq!(
+            stable_identifier("94006381012341", 8..13).unwrap(),
+            "94006381"
+        );
+        assert_eq!(
+            stable_identifier(" 94006381056789\n", 8..13).unwrap(),
+            "94006381"
+        );
+        assert_eq!(
+            stable_identifier("94006381056789", 1..4).unwrap(),
+            "9638105678"
+        );
+        assert_eq!(
+            stable_identifier("94006381056789", 13..13).unwrap(),
+            "9400638105678"
+        );
+        for code in &["14006381333938", "04006381333931", "4006381333931"] {
+            assert_eq!(stable_identifier(code, 8..13).unwrap(), fix(code).unwrap());
+        }
+    }
+
     proptest! {
+        #[test]
+        fn stable_identifier_is_not_a_gtin14(ref body in "9[0-9]{12}", start in 1usize..14) {
+            let code = recompute_check_digit(&format!("{}0", body)).unwrap();
+            let stable = stable_identifier(&code, start..13).unwrap();
+            assert!(stable.len() < 14);
+            assert_eq!(&stable[..], &code[..start]);
+        }
+    }
+
+    #[test]
+    fn stable_identifier_invalid() {
+        assert_eq!(
+            stable_identifier("94006381012341", 0..13),
+            Err(FixError::NotRepresentable)
+        );
+        assert_eq!(
+            stable_identifier("94006381012341", 8..14),
+            Err(FixError::NotRepresentable)
+        );
+        assert_eq!(
+            stable_identifier("94006381012342", 8..13),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn from_parts_static_data() {
+        let gtin = Gtin14::from_parts('1', "0614141", "00041").unwrap();
+        assert_eq!(gtin.as_str(), "10614141000415");
+        let gtin = Gtin14::from_parts('0', "400638133", "393").unwrap();
+        assert_eq!(gtin.as_str(), "04006381333931");
+    }
+
+    #[test]
+    fn from_parts_invalid() {
+        assert_eq!(
+            Gtin14::from_parts('1', "0614141", "000410"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin14),
+                trimmed_len: 14,
+            })
+        );
+        assert_eq!(
+            Gtin14::from_parts('1', "0614141", "0004"),
+            Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::from_parts('x', "0614141", "00041"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::from_parts('1', "061-141", "00041"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            Gtin14::from_parts('❤', "0614141", "00041"),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+    }
+
+    #[test]
+    fn looks_like_placeholder_same_digit() {
+        assert_eq!(looks_like_placeholder("00000000000000"), true);
+        assert_eq!(looks_like_placeholder("11111111111111"), true);
+        assert_eq!(looks_like_placeholder("99999999999999"), true);
+    }
+
+    #[test]
+    fn looks_like_placeholder_ascending() {
+        assert_eq!(looks_like_placeholder("12345678901231"), true);
+        assert_eq!(looks_like_placeholder("01234567890128"), true);
+        assert_eq!(looks_like_placeholder("67890123456780"), true);
+    }
+
+    #[test]
+    fn looks_like_placeholder_real_codes() {
+        assert_eq!(looks_like_placeholder("14567815983469"), false);
+        assert_eq!(looks_like_placeholder("04006381333931"), false);
+        assert_eq!(looks_like_placeholder("11111111111110"), false);
+        assert_eq!(looks_like_placeholder("12345678901321"), false);
+    }
+
+    #[test]
+    fn looks_like_placeholder_not_gtin14() {
+        assert_eq!(looks_like_placeholder("0000000000000"), false);
+        assert_eq!(looks_like_placeholder("aaaaaaaaaaaaaa"), false);
+        assert_eq!(looks_like_placeholder(""), false);
+    }
+
+    #[test]
+    fn fix_max_input_len() {
+        let mut padded = " ".repeat(MAX_INPUT_LEN - 14);
+        padded.push_str("14567815983469");
+        assert!(fix(&padded).is_ok());
+        padded.push(' ');
+        assert_eq!(fix(&padded), Err(FixError::TooLong { trimmed_len: 14 }));
+        assert_eq!(
+            fix(" ".repeat(1 << 20)),
+            Err(FixError::TooLong { trimmed_len: 0 })
+        );
+    }
+
+    #[test]
+    fn check_prefix14_with_serial() {
+        assert_eq!(check_prefix14("145678159834690000123"), true);
+        assert_eq!(check_prefix14("14567815983469-❤"), true);
+        assert_eq!(check_prefix14("04006381333931"), true);
+        assert_eq!(check_prefix14("145678159834680000123"), false); // Bad check digit
+        assert_eq!(check_prefix14(" 14567815983469"), false);
+        assert_eq!(check_prefix14("1456781598346❤"), false);
+        assert_eq!(check_prefix14(""), false);
+    }
+
+    #[test]
+    fn itf14_layout_pairs() {
+        let layout = itf14_layout("14567815983469").unwrap();
+        assert_eq!(
+            layout.pairs,
+            [(1, 4), (5, 6), (7, 8), (1, 5), (9, 8), (3, 4), (6, 9)]
+        );
+    }
+
+    #[test]
+    fn itf14_layout_invalid() {
+        assert_eq!(
+            itf14_layout("14567815983460"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 9,
+                found: 0
+            })
+        );
+        assert_eq!(itf14_layout("4006381333931"), Err(FixError::TooShort));
+        assert_eq!(
+            itf14_layout(" 14567815983469"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+        assert_eq!(
+            itf14_layout("1456781598346a"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(itf14_layout("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn padded_classifiers() {
+        // GTIN-8, GTIN-12, GTIN-13 and GTIN-14 codes, each padded to 14
+        let padded8 = "00000014567810";
+        let padded12 = "00897854613315";
+        let padded13 = "01498279802125";
+        let gtin14 = "14567815983469";
+
+        assert_eq!(is_gtin8_based(padded8), true);
+        assert_eq!(is_gtin12_based(padded8), true);
+        assert_eq!(is_gtin13_based(padded8), true);
+
+        assert_eq!(is_gtin8_based(padded12), false);
+        assert_eq!(is_gtin12_based(padded12), true);
+        assert_eq!(is_gtin13_based(padded12), true);
+
+        assert_eq!(is_gtin8_based(padded13), false);
+        assert_eq!(is_gtin12_based(padded13), false);
+        assert_eq!(is_gtin13_based(padded13), true);
+
+        assert_eq!(is_gtin8_based(gtin14), false);
+        assert_eq!(is_gtin12_based(gtin14), false);
+        assert_eq!(is_gtin13_based(gtin14), false);
+    }
+
+    #[test]
+    fn padded_classifiers_require_valid() {
+        assert_eq!(is_gtin8_based("00000014567811"), false);
+        assert_eq!(is_gtin12_based("0000001456781"), false);
+        assert_eq!(is_gtin13_based(""), false);
+        assert_eq!(shortest_kind("00000014567811"), None);
+    }
+
+    #[test]
+    fn shortest_kind_each_width() {
+        assert_eq!(shortest_kind("00000014567810"), Some(GtinKind::Gtin8));
+        assert_eq!(shortest_kind("00000000000000"), Some(GtinKind::Gtin8));
+        assert_eq!(shortest_kind("00897854613315"), Some(GtinKind::Gtin12));
+        assert_eq!(shortest_kind("01498279802125"), Some(GtinKind::Gtin13));
+        assert_eq!(shortest_kind("14567815983469"), Some(GtinKind::Gtin14));
+    }
+
+    #[test]
+    fn ensure_borrows_valid() {
+        match ensure("14567815983469") {
+            Ok(Cow::Borrowed(code)) => assert_eq!(code, "14567815983469"),
+            other => panic!("expected a borrowed code, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ensure_fixes_invalid() {
+        match ensure(" 036000291452\n") {
+            Ok(Cow::Owned(code)) => assert_eq!(code, "00036000291452"),
+            other => panic!("expected an owned code, got {:?}", other),
+        }
+        assert_eq!(
+            ensure("14567815983468"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 9,
+                found: 8
+            })
+        );
+        assert_eq!(ensure("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn fix_scanned_strips_identifier() {
+        assert_eq!(fix_scanned("]I014567815983469").unwrap(), "14567815983469");
+        assert_eq!(fix_scanned("]I114567815983469").unwrap(), "14567815983469");
+        assert_eq!(fix_scanned("14567815983469").unwrap(), "14567815983469");
+    }
+
+    #[test]
+    fn fix_scanned_appends_check_digit_after_i3() {
+        assert_eq!(fix_scanned("]I31456781598346").unwrap(), "14567815983469");
+        assert_eq!(fix_scanned("]I31046563414839").unwrap(), "10465634148394");
+        assert_eq!(
+            fix_scanned(" ]I31456781598346\r\n").unwrap(),
+            "14567815983469"
+        );
+        // The check digit was not sent, so a full code is too long
+        assert_eq!(
+            fix_scanned("]I314567815983469"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(fix_scanned("]I3406381333931"), Err(FixError::TooShort));
+        assert_eq!(
+            fix_scanned("]I34006381 33931"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(fix_scanned("]I3❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn fix_scanned_rejects_other_framing() {
+        assert!(fix_scanned("]I214567815983469").is_err());
+        assert!(fix_scanned("]E014567815983469").is_err());
+        assert!(fix_scanned("]I1]I114567815983469").is_err());
+        assert!(fix_scanned("14567815983469]I1").is_err());
+        assert_eq!(fix_scanned("]I❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn fix_lenient_extra_zero() {
+        assert_eq!(fix_lenient("004527819983417").unwrap(), "04527819983417");
+        assert_eq!(fix_lenient(" 014567815983469\n").unwrap(), "14567815983469");
+        assert_eq!(
+            fix("004527819983417"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+    }
+
+    #[test]
+    fn fix_lenient_otherwise_strict() {
+        assert_eq!(fix_lenient("04527819983417").unwrap(), "04527819983417");
+        assert_eq!(fix_lenient("4006381333931").unwrap(), "04006381333931");
+        assert_eq!(
+            fix_lenient("104527819983417"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+        // Only a single extra zero is removed
+        assert_eq!(
+            fix_lenient("0004527819983417"),
+            Err(FixError::TooLong { trimmed_len: 16 })
+        );
+        assert_eq!(
+            fix_lenient("004527819983418"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 7,
+                found: 8
+            })
+        );
+    }
+
+    #[test]
+    fn recompute_check_digit_overwrites() {
+        assert_eq!(
+            recompute_check_digit("14567815983460").unwrap(),
+            "14567815983469"
+        );
+        assert_eq!(
+            recompute_check_digit("14567815983468").unwrap(),
+            "14567815983469"
+        );
+        assert_eq!(
+            recompute_check_digit("14567815983469").unwrap(),
+            "14567815983469"
+        );
+        assert_eq!(
+            recompute_check_digit("00000000000009").unwrap(),
+            "00000000000000"
+        );
+    }
+
+    #[test]
+    fn recompute_check_digit_invalid() {
+        assert_eq!(
+            recompute_check_digit("1456781598346"),
+            Err(FixError::TooShort)
+        );
+        assert_eq!(
+            recompute_check_digit("145678159834690"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+        assert_eq!(
+            recompute_check_digit(" 1456781598346"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(
+            recompute_check_digit("1456781598346X"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(recompute_check_digit("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn fix_arrayvec_static_data() {
+        use super::fix_arrayvec;
+
+        assert_eq!(
+            fix_arrayvec("14567815983469").unwrap().as_str(),
+            "14567815983469"
+        );
+        assert_eq!(
+            fix_arrayvec("\t14567815983469 ").unwrap().as_str(),
+            "14567815983469"
+        );
+        assert_eq!(
+            fix_arrayvec("4006381333931").unwrap().as_str(),
+            "04006381333931"
+        );
+        assert_eq!(fix_arrayvec("❤"), Err(FixError::NonAsciiString));
+        assert_eq!(
+            fix_arrayvec("145678159834690"),
+            Err(FixError::TooLong { trimmed_len: 15 })
+        );
+        assert_eq!(
+            fix_arrayvec("14567815983468"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 9,
+                found: 8
+            })
+        );
+    }
+
+    #[test]
+    fn fix_with_input_keeps_input() {
+        let kept = |input| {
+            if cfg!(feature = "error-input") {
+                Some(input)
+            } else {
+                None
+            }
+        };
+        assert_eq!(fix_with_input("14567815983469").unwrap(), "14567815983469");
+        let err = fix_with_input(" 14567815983468\n").unwrap_err();
+        assert_eq!(err.input(), kept("14567815983468"));
+        assert_eq!(
+            err.into_error(),
+            FixError::CheckDigitIncorrect {
+                expected: 9,
+                found: 8
+            }
+        );
+        assert_eq!(fix_with_input("❤").unwrap_err().input(), kept("❤"));
+
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(fix_with_input(&long).unwrap_err().input(), None);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    proptest! {
+        #[test]
+        fn fix_arrayvec_matches_fix(ref s in ".*") {
+            let stack = super::fix_arrayvec(s).map(|fixed| fixed.to_string());
+            assert_eq!(stack, fix(s));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn recompute_check_digit_result_is_valid(ref s in "[0-9]{14}") {
+            let fixed = recompute_check_digit(s).unwrap();
+            assert!(check(&fixed));
+            assert_eq!(&fixed[..13], &s[..13]);
+        }
+
+        #[test]
+        fn check_prefix14_doesnt_crash(ref s in ".*") {
+            let _ = check_prefix14(s);
+        }
+
+        #[test]
+        fn check_prefix14_ignores_suffix(ref s in "[0-9]{14}", ref suffix in ".*") {
+            assert_eq!(check_prefix14(&format!("{}{}", s, suffix)), check(s));
+        }
+
+        #[test]
+        fn fix_scanned_doesnt_crash(ref s in ".*") {
+            if let Ok(fixed) = fix_scanned(s) {
+                assert!(check(&fixed));
+            }
+        }
+
         #[test]
         fn doesnt_crash(ref s in ".*") {
-            check(s);
+            let _ = check(s);
+        }
+
+        #[test]
+        fn looks_like_placeholder_doesnt_crash(ref s in ".*") {
+            looks_like_placeholder(s);
+        }
+
+        #[test]
+        fn from_parts_is_valid(ref prefix in "[0-9]{6,10}", indicator in 0u8..10) {
+            let item = &"000000"[..12 - prefix.len()];
+            let gtin = Gtin14::from_parts(char::from(b'0' + indicator), prefix, item).unwrap();
+            assert!(check(gtin.as_str()));
+        }
+
+        #[test]
+        fn try_from_bytes_matches_check(ref bytes in prop::collection::vec(any::<u8>(), 0..20)) {
+            let valid = ::std::str::from_utf8(bytes).map(check).unwrap_or(false);
+            assert_eq!(Gtin14::try_from(&bytes[..]).is_ok(), valid);
+        }
+
+        #[test]
+        fn fix_doesnt_crash(ref bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+            if let Ok(fixed) = fix(String::from_utf8_lossy(bytes)) {
+                assert!(check(&fixed));
+            }
+        }
+
+        #[test]
+        fn fix_digits_doesnt_crash(ref s in "[0-9 \\t\\r\\n]{0,20}") {
+            if let Ok(fixed) = fix(s) {
+                assert!(check(&fixed));
+            }
+        }
+
+        #[test]
+        fn check_ai01_doesnt_crash(ref s in ".*") {
+            check_ai01(s);
         }
     }
 }
@@ -1,21 +1,92 @@
 //! Performs validation and correction of GTIN-8 codes.
 
+use context::InputError;
 use utils;
 
 /// Errors that make GTIN-8 correction impossible.
+///
+/// The `FixError` types of all GTIN modules share the same variants, so
+/// this type can be converted from any of them with `From`/`Into`. Each
+/// variant maps to the variant of the same name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FixError {
     /// The provided string contains non-ASCII characters.
     NonAsciiString,
+    /// The provided string contains characters other than ASCII digits.
+    NonNumericString,
     /// The provided code was too long to be valid.
-    TooLong,
+    TooLong {
+        /// The length of the code in bytes, after any whitespace
+        /// trimming done by the function returning the error. A length
+        /// within the expected width means that the input was rejected
+        /// only because of its surrounding whitespace.
+        trimmed_len: usize,
+    },
+    /// The provided code was too short to be valid.
+    TooShort,
     /// The calculated check-digit did not match the code's check-digit.
-    CheckDigitIncorrect,
+    CheckDigitIncorrect {
+        /// The check digit computed from the other digits of the code.
+        expected: u8,
+        /// The check digit the code ends with.
+        found: u8,
+    },
+    /// More than one correction of the code is possible, so none was
+    /// chosen.
+    Ambiguous,
+    /// The code is valid, but cannot be used for or converted into the
+    /// requested form.
+    NotRepresentable,
+}
+
+impl_fix_error_from!(gtin12, gtin13, gtin14);
+
+/// The longest input, in bytes, that [`fix`](fn.fix.html) will examine.
+///
+/// This is the 8-digit code plus an allowance for surrounding
+/// whitespace. Longer input is rejected with `TooLong` before it is
+/// copied, which bounds the work done on pathological input. The error
+/// still reports the trimmed length, so input that was only too long
+/// because of its whitespace can be told apart.
+pub const MAX_INPUT_LEN: usize = 8 + 32;
+
+gtin_newtype! {
+    /// A validated GTIN-8 code.
+    ///
+    /// Values can only be constructed from input that passes
+    /// [`check`](fn.check.html), so the wrapped string is always a valid
+    /// code. Construct them with `TryFrom`, from either a `&str` or the raw
+    /// bytes of the code.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::gtin8::Gtin8;
+    /// use std::convert::TryFrom;
+    ///
+    /// let code = Gtin8::try_from(&b"14567810"[..]).unwrap();
+    /// assert_eq!(code.as_str(), "14567810");
+    /// assert!(Gtin8::try_from("14567811").is_err()); // Bad check digit
+    /// ```
+    pub struct Gtin8(8);
+}
+
+/// The use of a GTIN-8 code as judged from its prefix, as returned by
+/// [`voucher_kind`](fn.voucher_kind.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VoucherKind {
+    /// A code in a restricted circulation range, which retailers use for
+    /// in-store vouchers and coupons as well as store-local items.
+    StoreVoucher,
+    /// A globally assigned code identifying a product.
+    Product,
 }
 
 /// Check that a GTIN-8 code is valid by confirming that it is exactly
 /// 8 digits in length and that the check-digit is correct.
 ///
+/// The code may be any string type, such as `&str`, `String` or
+/// `Cow<str>`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin8;
@@ -23,23 +94,64 @@ pub enum FixError {
 /// assert_eq!(gtin8::check("14567810"), true);  // Valid GTIN-8
 /// assert_eq!(gtin8::check("1456781"), false);  // too short
 /// assert_eq!(gtin8::check("14567811"), false); // Bad check digit
+/// assert_eq!(gtin8::check(String::from("14567810")), true);
 /// ```
-pub fn check(code: &str) -> bool {
-    if code.len() != 8 {
-        return false;
-    }
-    if !utils::is_ascii_numeric(code) {
-        return false;
+#[must_use = "the result of the check tells whether the code is valid"]
+pub fn check(code: impl AsRef<str>) -> bool {
+    fn inner(code: &str) -> bool {
+        if code.is_empty() {
+            return false;
+        }
+        if code.len() != 8 {
+            return false;
+        }
+        if !utils::is_ascii_numeric(code) {
+            return false;
+        }
+
+        // Calculate and compare check digit
+        let bytes = code.as_bytes();
+        let check = utils::compute_check_digit(bytes);
+        if check != bytes[7] - 48 {
+            return false;
+        }
+
+        true
     }
 
-    // Calculate and compare check digit
-    let bytes = code.as_bytes();
-    let check = utils::compute_check_digit(bytes);
-    if check != bytes[7] - 48 {
-        return false;
+    inner(code.as_ref())
+}
+
+/// Check a GTIN-8 code given as an array of exactly 8 ASCII bytes.
+///
+/// The result is the same as [`check`](fn.check.html) for the same
+/// digits, but the length is fixed by the type, so there is no length
+/// branch and the compiler can fully unroll the loop. This function does
+/// not allocate or depend on `std`.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8;
+///
+/// assert_eq!(gtin8::check_array(b"14567810"), true);
+/// assert_eq!(gtin8::check_array(b"14567811"), false); // Bad check digit
+/// assert_eq!(gtin8::check_array(b"1456781A"), false);
+/// ```
+#[must_use = "the result of the check tells whether the code is valid"]
+pub fn check_array(code: &[u8; 8]) -> bool {
+    const WEIGHTS: [u8; 8] = [3, 1, 3, 1, 3, 1, 3, 1];
+
+    let mut sum: u8 = 0;
+    let mut digits = true;
+    for i in 0..8 {
+        let digit = code[i].wrapping_sub(b'0');
+        digits &= digit < 10;
+        // Each term is at most 27 and the sum at most 120, so the sum
+        // fits in a u8 for digit input; wrapping keeps other input safe.
+        sum = sum.wrapping_add(WEIGHTS[i].wrapping_mul(digit));
     }
 
-    true
+    digits && sum.is_multiple_of(10)
 }
 
 /// Attempt to fix an invalid GTIN-8 code by stripping whitespace from
@@ -50,6 +162,10 @@ pub fn check(code: &str) -> bool {
 /// entry and software that treats GTINs as integers rather than
 /// strings, thus truncating the leading zeros.
 ///
+/// As with [`check`](fn.check.html), the code may be any string type.
+/// Input longer than [`MAX_INPUT_LEN`](constant.MAX_INPUT_LEN.html)
+/// bytes is rejected with `TooLong`.
+///
 /// # Examples
 /// ```
 /// use gtin_validate::gtin8;
@@ -70,32 +186,233 @@ pub fn check(code: &str) -> bool {
 /// ```
 /// # use gtin_validate::gtin8;
 /// match gtin8::fix("14567811") {
-///   Ok(fixed) => {println!("Fixed GTIN-14: {}", fixed);}
-///   Err(_) => {println!("Could not fix GTIN-14");}
+///   Ok(fixed) => {println!("Fixed GTIN-8: {}", fixed);}
+///   Err(_) => {println!("Could not fix GTIN-8");}
 /// }
 /// ```
-pub fn fix(code: &str) -> Result<String, FixError> {
-    let mut fixed = code.trim().to_string();
+#[must_use = "the fixed code is returned rather than modified in place"]
+pub fn fix(code: impl AsRef<str>) -> Result<String, FixError> {
+    #[inline]
+    fn inner(code: &str) -> Result<String, FixError> {
+        if code.len() > MAX_INPUT_LEN {
+            return Err(FixError::TooLong {
+                trimmed_len: code.trim().len(),
+            });
+        }
+        let trimmed = code.trim();
+
+        if !trimmed.is_ascii() {
+            return Err(FixError::NonAsciiString);
+        }
+        if trimmed.len() > 8 {
+            return Err(FixError::TooLong {
+                trimmed_len: trimmed.len(),
+            });
+        }
+        if trimmed.len() < 8 {
+            return fix_padded(trimmed);
+        }
+        if !check(trimmed) {
+            return Err(check_digit_error(trimmed));
+        }
 
-    if !fixed.is_ascii() {
-        return Err(FixError::NonAsciiString);
+        Ok(trimmed.to_string())
     }
-    if fixed.len() > 8 {
-        return Err(FixError::TooLong);
+
+    inner(code.as_ref())
+}
+
+// Codes that need padding are the uncommon case, so keep this path out
+// of line to leave the full-length path in `fix` small.
+#[cold]
+#[inline(never)]
+fn fix_padded(trimmed: &str) -> Result<String, FixError> {
+    let padded = utils::zero_pad(trimmed, 8).into_owned();
+    if !check(&padded) {
+        return Err(check_digit_error(&padded));
     }
-    fixed = utils::zero_pad(fixed, 8);
-    if !check(&fixed) {
-        return Err(FixError::CheckDigitIncorrect);
+
+    Ok(padded)
+}
+
+/// Fix a GTIN-8 code as [`fix`](fn.fix.html) does, keeping a copy of the
+/// trimmed input in the error.
+///
+/// The copy is only made when `fix` fails, and only with the
+/// `error-input` feature. See [`InputError`](../struct.InputError.html).
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8;
+///
+/// let err = gtin8::fix_with_input("14567811 ").unwrap_err();
+/// #[cfg(feature = "error-input")]
+/// assert_eq!(err.input(), Some("14567811"));
+/// ```
+pub fn fix_with_input(code: &str) -> Result<String, InputError<FixError>> {
+    fix(code).map_err(|error| InputError::new(error, code, MAX_INPUT_LEN))
+}
+
+/// Check whether a GTIN-8 code is a restricted circulation number,
+/// meant for use within a company (such as in-store items or coupons)
+/// rather than a globally unique product identifier.
+///
+/// GS1 reserves two ranges of GTIN-8 prefixes for restricted
+/// circulation, separately from the GTIN-13 ranges:
+///
+/// * `0`: codes `00000000` to `09999999`
+/// * `2`: codes `20000000` to `29999999`
+///
+/// All other GTIN-8 codes are assigned globally by GS1 member
+/// organizations. Codes that fail [`check`](fn.check.html) are never
+/// considered restricted.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8;
+///
+/// assert_eq!(gtin8::is_restricted("20000004"), true);  // Store-local
+/// assert_eq!(gtin8::is_restricted("14567810"), false); // Globally unique
+/// assert_eq!(gtin8::is_restricted("20000005"), false); // Bad check digit
+/// ```
+pub fn is_restricted(code: &str) -> bool {
+    check(code) && (code.starts_with('0') || code.starts_with('2'))
+}
+
+/// Check whether a GTIN-8 code is likely a native EAN-8, assigned by GS1
+/// as a standalone product identifier, rather than a compressed form of
+/// a longer code.
+///
+/// This is a heuristic based on the first digit of a code that passes
+/// [`check`](fn.check.html):
+///
+/// * `0` and `1` are the number systems used by UPC-E, the 8-digit
+///   compression of a UPC-A code. Such a code may be a UPC-E symbol whose
+///   digits happen to form a valid GTIN-8, so it is not considered
+///   native.
+/// * `2` is a restricted circulation prefix (see
+///   [`is_restricted`](fn.is_restricted.html)), assigned within a company
+///   rather than by GS1, so it is not considered native.
+/// * Every other prefix is considered native.
+///
+/// The digits alone cannot settle the question: a genuinely assigned
+/// EAN-8 starting with `0` or `1` is reported as not native, and a code
+/// that is really the compressed form of something else is only caught
+/// if it uses one of the prefixes above. Where the symbology is known
+/// from the scanner (see
+/// [`strip_aim_identifier`](../gtin13/fn.strip_aim_identifier.html)),
+/// prefer that.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8;
+///
+/// assert_eq!(gtin8::is_native("96385074"), true);
+/// assert_eq!(gtin8::is_native("01234565"), false); // Possibly UPC-E
+/// assert_eq!(gtin8::is_native("20000004"), false); // Store-local
+/// assert_eq!(gtin8::is_native("96385075"), false); // Bad check digit
+/// ```
+pub fn is_native(code: &str) -> bool {
+    check(code) && !(code.starts_with('0') || code.starts_with('1') || is_restricted(code))
+}
+
+/// Classify a valid GTIN-8 code as a possible in-store voucher or as a
+/// product, or return `None` if the code fails [`check`](fn.check.html).
+///
+/// The classification is based only on the reserved prefix ranges of
+/// [`is_restricted`](fn.is_restricted.html): codes starting with `0` or
+/// `2` are reported as `VoucherKind::StoreVoucher`, and all others as
+/// `VoucherKind::Product`. This is the parallel of
+/// [`gtin12::is_coupon`](../gtin12/fn.is_coupon.html) for the 8-digit
+/// format, but it is advisory only. GS1 does not reserve an EAN-8 range
+/// for vouchers, so a restricted code may equally be a store-local item,
+/// and only the retailer that assigned it knows which.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8::{self, VoucherKind};
+///
+/// assert_eq!(gtin8::voucher_kind("21234569"), Some(VoucherKind::StoreVoucher));
+/// assert_eq!(gtin8::voucher_kind("96385074"), Some(VoucherKind::Product));
+/// assert_eq!(gtin8::voucher_kind("21234560"), None); // Bad check digit
+/// ```
+pub fn voucher_kind(code: &str) -> Option<VoucherKind> {
+    if !check(code) {
+        return None;
     }
 
-    Ok(fixed)
+    if is_restricted(code) {
+        Some(VoucherKind::StoreVoucher)
+    } else {
+        Some(VoucherKind::Product)
+    }
+}
+
+/// Convert a GTIN-8 code to the equivalent GTIN-13.
+///
+/// The code is corrected with [`fix`](fn.fix.html) first, then padded
+/// with leading zeros. The check digit is weighted from the right, so
+/// the leading zeros leave it unchanged and the result always passes
+/// [`gtin13::check`](../gtin13/fn.check.html).
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8;
+///
+/// assert_eq!(gtin8::to_gtin13("14567810").unwrap(), "0000014567810");
+/// assert!(gtin8::to_gtin13("14567811").is_err()); // Bad check digit
+/// ```
+pub fn to_gtin13(code: &str) -> Result<String, FixError> {
+    Ok(utils::zero_pad(&fix(code)?, 13).into_owned())
+}
+
+/// Convert a GTIN-8 code to the equivalent GTIN-14, with indicator 0.
+///
+/// As with [`to_gtin13`](fn.to_gtin13.html), the code is corrected with
+/// `fix` and padded with leading zeros, so the result always passes
+/// [`gtin14::check`](../gtin14/fn.check.html).
+///
+/// # Examples
+/// ```
+/// use gtin_validate::gtin8;
+///
+/// assert_eq!(gtin8::to_gtin14("14567810").unwrap(), "00000014567810");
+/// ```
+pub fn to_gtin14(code: &str) -> Result<String, FixError> {
+    Ok(utils::zero_pad(&fix(code)?, 14).into_owned())
+}
+
+// Describe why a code of the full width failed `check`: either it is
+// not all digits, or its check digit is wrong.
+fn check_digit_error(code: &str) -> FixError {
+    if !utils::is_ascii_numeric(code) {
+        return FixError::NonNumericString;
+    }
+    let (expected, found) = utils::check_digit_pair(code.as_bytes());
+    FixError::CheckDigitIncorrect { expected, found }
 }
 
 #[cfg(test)]
 mod tests {
     use super::check;
+    use super::check_array;
     use super::fix;
+    use super::fix_with_input;
+    use super::is_native;
+    use super::is_restricted;
+    use super::to_gtin13;
+    use super::to_gtin14;
+    use super::voucher_kind;
     use super::FixError;
+    use super::Gtin8;
+    use super::VoucherKind;
+    use super::MAX_INPUT_LEN;
+    use gtin::{GtinError, GtinKind};
+    use gtin13;
+    use gtin14;
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
 
     #[test]
     fn check_valid() {
@@ -149,12 +466,37 @@ mod tests {
 
     #[test]
     fn fix_too_long() {
-        assert_eq!(fix("000000000"), Err(FixError::TooLong));
+        assert_eq!(fix("000000000"), Err(FixError::TooLong { trimmed_len: 9 }));
     }
 
     #[test]
     fn fix_incorrect_check_digit() {
-        assert_eq!(fix("14567813"), Err(FixError::CheckDigitIncorrect));
+        assert_eq!(
+            fix("14567813"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 0,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn fix_full_length_incorrect_check_digit() {
+        // Already 8 digits, so no padding is attempted
+        assert_eq!(
+            fix("14567811"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 0,
+                found: 1
+            })
+        );
+        assert_eq!(
+            fix(" 14567811\n"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 0,
+                found: 1
+            })
+        );
     }
 
     #[test]
@@ -164,10 +506,275 @@ mod tests {
         assert_eq!(fix("9944220").unwrap(), "09944220");
     }
 
+    #[test]
+    fn fix_error_from_other_modules() {
+        use gtin12;
+        use gtin14;
+        assert_eq!(
+            FixError::from(gtin12::FixError::NonAsciiString),
+            FixError::NonAsciiString
+        );
+        assert_eq!(
+            FixError::from(gtin12::FixError::TooLong { trimmed_len: 15 }),
+            FixError::TooLong { trimmed_len: 15 }
+        );
+        let err: FixError = gtin14::FixError::CheckDigitIncorrect {
+            expected: 1,
+            found: 2,
+        }
+        .into();
+        assert_eq!(
+            err,
+            FixError::CheckDigitIncorrect {
+                expected: 1,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_valid() {
+        let code = Gtin8::try_from(&b"14567810"[..]).unwrap();
+        assert_eq!(code.as_str(), "14567810");
+        assert_eq!(Gtin8::try_from("14567810"), Ok(code));
+    }
+
+    #[test]
+    fn digit_at_bounds() {
+        let code = Gtin8::try_from("14567810").unwrap();
+        assert_eq!(code.digit_at(0), Some(1));
+        assert_eq!(code.digit_at(7), Some(0));
+        assert_eq!(code.digit_at(8), None);
+    }
+
+    #[test]
+    fn try_from_bytes_invalid() {
+        assert_eq!(
+            Gtin8::try_from(&b"\xff\xfe"[..]),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin8),
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from("❤"),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin8),
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from("145678100"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin8),
+                trimmed_len: 9,
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from("4567810"),
+            Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin8),
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from(" 4567810"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin8),
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from("a4567810"),
+            Err(GtinError::NonNumericString {
+                kind: Some(GtinKind::Gtin8),
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from("14567811"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin8),
+                expected: 0,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn fix_max_input_len() {
+        let mut padded = " ".repeat(MAX_INPUT_LEN - 8);
+        padded.push_str("14567810");
+        assert!(fix(&padded).is_ok());
+        padded.push(' ');
+        assert_eq!(fix(&padded), Err(FixError::TooLong { trimmed_len: 8 }));
+        assert_eq!(
+            fix(" ".repeat(1 << 20)),
+            Err(FixError::TooLong { trimmed_len: 0 })
+        );
+    }
+
+    #[test]
+    fn is_native_prefixes() {
+        assert_eq!(is_native("01234565"), false);
+        assert_eq!(is_native("12345670"), false);
+        assert_eq!(is_native("20000004"), false);
+        assert_eq!(is_native("30000001"), true);
+        assert_eq!(is_native("50000005"), true);
+        assert_eq!(is_native("96385074"), true);
+    }
+
+    #[test]
+    fn is_native_invalid() {
+        assert_eq!(is_native("50000006"), false);
+        assert_eq!(is_native(" 50000005"), false);
+        assert_eq!(is_native(""), false);
+        assert_eq!(is_native("❤"), false);
+    }
+
+    #[test]
+    fn is_restricted_boundaries() {
+        assert_eq!(is_restricted("00000000"), true);
+        assert_eq!(is_restricted("09999992"), true);
+        assert_eq!(is_restricted("10000007"), false);
+        assert_eq!(is_restricted("19999999"), false);
+        assert_eq!(is_restricted("20000004"), true);
+        assert_eq!(is_restricted("29999996"), true);
+        assert_eq!(is_restricted("30000001"), false);
+        assert_eq!(is_restricted("99999995"), false);
+    }
+
+    #[test]
+    fn is_restricted_invalid() {
+        assert_eq!(is_restricted("0000000"), false);
+        assert_eq!(is_restricted(" 0000000"), false);
+        assert_eq!(is_restricted("20000005"), false);
+        assert_eq!(is_restricted("❤"), false);
+    }
+
+    #[test]
+    fn voucher_kind_static_data() {
+        assert_eq!(voucher_kind("21234569"), Some(VoucherKind::StoreVoucher));
+        assert_eq!(voucher_kind("07654329"), Some(VoucherKind::StoreVoucher));
+        assert_eq!(voucher_kind("29999996"), Some(VoucherKind::StoreVoucher));
+        assert_eq!(voucher_kind("14567810"), Some(VoucherKind::Product));
+        assert_eq!(voucher_kind("30000001"), Some(VoucherKind::Product));
+        assert_eq!(voucher_kind("96385074"), Some(VoucherKind::Product));
+    }
+
+    #[test]
+    fn voucher_kind_invalid() {
+        assert_eq!(voucher_kind("21234560"), None);
+        assert_eq!(voucher_kind(" 21234569"), None);
+        assert_eq!(voucher_kind("2123456"), None);
+        assert_eq!(voucher_kind("❤"), None);
+    }
+
+    #[test]
+    fn to_gtin13_static_data() {
+        assert_eq!(to_gtin13("14567810").unwrap(), "0000014567810");
+        assert_eq!(to_gtin13(" 49137712").unwrap(), "0000049137712");
+        assert_eq!(to_gtin13("0").unwrap(), "0000000000000");
+    }
+
+    #[test]
+    fn to_gtin14_static_data() {
+        assert_eq!(to_gtin14("14567810").unwrap(), "00000014567810");
+        assert_eq!(to_gtin14("49137712 ").unwrap(), "00000049137712");
+    }
+
+    #[test]
+    fn to_wider_errors() {
+        assert_eq!(
+            to_gtin13("14567811"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 0,
+                found: 1
+            })
+        );
+        assert_eq!(
+            to_gtin14("145678100"),
+            Err(FixError::TooLong { trimmed_len: 9 })
+        );
+        assert_eq!(to_gtin14("❤"), Err(FixError::NonAsciiString));
+    }
+
+    #[test]
+    fn check_array_static_data() {
+        assert_eq!(check_array(b"14567810"), true);
+        assert_eq!(check_array(b"49137712"), true);
+        assert_eq!(check_array(b"00000000"), true);
+        assert_eq!(check_array(b"14567811"), false);
+        assert_eq!(check_array(b" 4567810"), false);
+        assert_eq!(check_array(&[0xff; 8]), false);
+    }
+
+    #[test]
+    fn fix_with_input_keeps_input() {
+        let kept = |input| {
+            if cfg!(feature = "error-input") {
+                Some(input)
+            } else {
+                None
+            }
+        };
+        assert_eq!(fix_with_input("14567810").unwrap(), "14567810");
+        let err = fix_with_input(" 14567811\n").unwrap_err();
+        assert_eq!(err.input(), kept("14567811"));
+        assert_eq!(
+            err.into_error(),
+            FixError::CheckDigitIncorrect {
+                expected: 0,
+                found: 1
+            }
+        );
+        assert_eq!(fix_with_input("❤").unwrap_err().input(), kept("❤"));
+
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert_eq!(fix_with_input(&long).unwrap_err().input(), None);
+    }
+
     proptest! {
+        #[test]
+        fn check_array_matches_check(ref code in prop::array::uniform8(any::<u8>())) {
+            let valid = ::std::str::from_utf8(code).map(check).unwrap_or(false);
+            assert_eq!(check_array(code), valid);
+        }
+
+        #[test]
+        fn check_array_matches_check_digits(ref s in "[0-9]{8}") {
+            let mut code = [0; 8];
+            code.copy_from_slice(s.as_bytes());
+            assert_eq!(check_array(&code), check(s));
+        }
+
+        #[test]
+        fn to_wider_passes_check(ref s in "[0-9]{8}") {
+            assert_eq!(to_gtin13(s).is_ok(), check(s));
+            if let Ok(code) = to_gtin13(s) {
+                assert!(gtin13::check(&code));
+                assert!(gtin14::check(to_gtin14(s).unwrap()));
+            }
+        }
+
         #[test]
         fn doesnt_crash(ref s in ".*") {
-            check(s);
+            let _ = check(s);
+        }
+
+        #[test]
+        fn try_from_bytes_matches_check(ref bytes in prop::collection::vec(any::<u8>(), 0..20)) {
+            let valid = ::std::str::from_utf8(bytes).map(check).unwrap_or(false);
+            assert_eq!(Gtin8::try_from(&bytes[..]).is_ok(), valid);
+        }
+
+        #[test]
+        fn fix_doesnt_crash(ref bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+            if let Ok(fixed) = fix(String::from_utf8_lossy(bytes)) {
+                assert!(check(&fixed));
+            }
+        }
+
+        #[test]
+        fn fix_digits_doesnt_crash(ref s in "[0-9 \\t\\r\\n]{0,20}") {
+            if let Ok(fixed) = fix(s) {
+                assert!(check(&fixed));
+            }
         }
     }
 }
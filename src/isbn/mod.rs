@@ -0,0 +1,270 @@
+//! Performs validation of ISBN-10 codes and their conversion to
+//! Bookland GTIN-13 (ISBN-13) codes, and normalization of ISBN-13 codes
+//! in their hyphenated display form.
+//!
+//! Unlike GTINs, ISBN-10 codes use a mod-11 check digit, which can take
+//! the value 10, written as `X`.
+
+use gtin13;
+use gtin13::FixError;
+use utils;
+
+/// Check that an ISBN-10 code is valid by confirming that it is made of
+/// nine digits followed by a digit or `X` check character, and that the
+/// mod-11 check digit is correct.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::isbn;
+///
+/// assert_eq!(isbn::check_isbn10("0131103628"), true);  // Valid ISBN-10
+/// assert_eq!(isbn::check_isbn10("080442957X"), true);  // Check digit 10
+/// assert_eq!(isbn::check_isbn10("0131103627"), false); // Bad check digit
+/// ```
+pub fn check_isbn10(code: &str) -> bool {
+    if code.len() != 10 {
+        return false;
+    }
+    let bytes = code.as_bytes();
+    if !bytes[..9].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+
+    let check = match bytes[9] {
+        b'X' | b'x' => 10,
+        b @ b'0'..=b'9' => u16::from(b - b'0'),
+        _ => return false,
+    };
+
+    // Weights run from 10 for the first digit down to 1 for the check
+    // digit, and a valid code sums to a multiple of 11.
+    let sum = utils::compute_weighted(&bytes[..9], &[2, 3, 4, 5, 6, 7, 8, 9, 10], 11);
+    (sum + check).is_multiple_of(11)
+}
+
+/// Convert an ISBN-10 code to the equivalent ISBN-13, which is a
+/// GTIN-13 in the `978` Bookland prefix.
+///
+/// The ISBN-10 is validated first, then its check digit is dropped,
+/// `978` is prepended and the GTIN-13 check digit is computed, so the
+/// result always passes [`gtin13::check`](../gtin13/fn.check.html).
+/// For an ISBN-10 with the wrong check digit, the `CheckDigitIncorrect`
+/// error reports a check character of `X` as 10.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{gtin13, isbn};
+///
+/// let isbn13 = isbn::isbn10_to_isbn13("0131103628").unwrap();
+/// assert_eq!(isbn13, "9780131103627");
+/// assert_eq!(gtin13::check(&isbn13), true);
+///
+/// assert!(isbn::isbn10_to_isbn13("0131103627").is_err()); // Bad check digit
+/// ```
+pub fn isbn10_to_isbn13(code: &str) -> Result<String, FixError> {
+    if !code.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    if code.len() > 10 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 10 {
+        return Err(FixError::TooShort);
+    }
+    if !check_isbn10(code) {
+        return Err(isbn10_error(code));
+    }
+
+    let mut isbn13 = String::with_capacity(13);
+    isbn13.push_str("978");
+    isbn13.push_str(&code[..9]);
+    let check = gtin13::check_digit_for_prefix(&isbn13).ok_or(FixError::NonNumericString)?;
+    isbn13.push(char::from(b'0' + check));
+
+    Ok(isbn13)
+}
+
+/// Normalize an ISBN-13 in its hyphenated display form, such as
+/// `978-0-13-110362-7`, to the bare 13-digit code.
+///
+/// The hyphens are removed, then the remaining characters must be
+/// exactly 13 digits forming a valid GTIN-13 in the `978` or `979`
+/// Bookland prefix. A valid GTIN-13 outside those prefixes is rejected
+/// with `NotRepresentable`. No other separators or whitespace are
+/// accepted.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::isbn;
+///
+/// assert_eq!(isbn::normalize_isbn13("978-0-13-110362-7").unwrap(), "9780131103627");
+/// assert_eq!(isbn::normalize_isbn13("9780131103627").unwrap(), "9780131103627");
+/// assert!(isbn::normalize_isbn13("978-0-13-110362-8").is_err()); // Bad check digit
+/// ```
+pub fn normalize_isbn13(display: &str) -> Result<String, FixError> {
+    if !display.is_ascii() {
+        return Err(FixError::NonAsciiString);
+    }
+    let code: String = display.chars().filter(|&c| c != '-').collect();
+
+    if !code.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(FixError::NonNumericString);
+    }
+    if code.len() > 13 {
+        return Err(FixError::TooLong {
+            trimmed_len: code.len(),
+        });
+    }
+    if code.len() < 13 {
+        return Err(FixError::TooShort);
+    }
+    if !gtin13::check(&code) {
+        let (expected, found) = utils::check_digit_pair(code.as_bytes());
+        return Err(FixError::CheckDigitIncorrect { expected, found });
+    }
+    if !code.starts_with("978") && !code.starts_with("979") {
+        return Err(FixError::NotRepresentable);
+    }
+
+    Ok(code)
+}
+
+// Describe why a 10-character code failed `check_isbn10`. A check
+// character of `X` is reported as 10.
+fn isbn10_error(code: &str) -> FixError {
+    let bytes = code.as_bytes();
+    let found = match bytes[9] {
+        b'X' | b'x' => 10,
+        b if b.is_ascii_digit() => b - b'0',
+        _ => return FixError::NonNumericString,
+    };
+    if !utils::is_ascii_numeric(&code[..9]) {
+        return FixError::NonNumericString;
+    }
+
+    let sum = utils::compute_weighted(&bytes[..9], &[2, 3, 4, 5, 6, 7, 8, 9, 10], 11);
+    FixError::CheckDigitIncorrect {
+        expected: ((11 - sum) % 11) as u8,
+        found,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_isbn10;
+    use super::isbn10_to_isbn13;
+    use super::normalize_isbn13;
+    use gtin13;
+    use gtin13::FixError;
+
+    #[test]
+    fn check_isbn10_valid() {
+        assert_eq!(check_isbn10("0131103628"), true);
+        assert_eq!(check_isbn10("0306406152"), true);
+        assert_eq!(check_isbn10("080442957X"), true);
+        assert_eq!(check_isbn10("080442957x"), true);
+        assert_eq!(check_isbn10("0000000000"), true);
+    }
+
+    #[test]
+    fn check_isbn10_invalid() {
+        assert_eq!(check_isbn10(""), false);
+        assert_eq!(check_isbn10("013110362"), false); // too short
+        assert_eq!(check_isbn10("01311036288"), false); // too long
+        assert_eq!(check_isbn10("0131103627"), false); // bad check digit
+        assert_eq!(check_isbn10("0804429579"), false); // should be X
+        assert_eq!(check_isbn10("X131103628"), false); // X only at the end
+        assert_eq!(check_isbn10("013110362-"), false);
+        assert_eq!(check_isbn10("❤❤❤❤"), false);
+        assert_eq!(check_isbn10("1234567❤"), false); // 10 bytes
+    }
+
+    #[test]
+    fn isbn10_to_isbn13_static_data() {
+        assert_eq!(isbn10_to_isbn13("0131103628").unwrap(), "9780131103627");
+        assert_eq!(isbn10_to_isbn13("080442957X").unwrap(), "9780804429573");
+        assert_eq!(isbn10_to_isbn13("0306406152").unwrap(), "9780306406157");
+    }
+
+    #[test]
+    fn isbn10_to_isbn13_errors() {
+        assert_eq!(isbn10_to_isbn13("❤"), Err(FixError::NonAsciiString));
+        assert_eq!(
+            isbn10_to_isbn13("01311036288"),
+            Err(FixError::TooLong { trimmed_len: 11 })
+        );
+        assert_eq!(isbn10_to_isbn13("013110362"), Err(FixError::TooShort));
+        assert_eq!(
+            isbn10_to_isbn13("0131103627"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 8,
+                found: 7
+            })
+        );
+    }
+
+    #[test]
+    fn normalize_isbn13_static_data() {
+        assert_eq!(
+            normalize_isbn13("978-0-13-110362-7").unwrap(),
+            "9780131103627"
+        );
+        assert_eq!(
+            normalize_isbn13("978-0-306-40615-7").unwrap(),
+            "9780306406157"
+        );
+        assert_eq!(normalize_isbn13("9780804429573").unwrap(), "9780804429573");
+        assert_eq!(
+            normalize_isbn13("979-10-90636-07-1").unwrap(),
+            "9791090636071"
+        );
+    }
+
+    #[test]
+    fn normalize_isbn13_errors() {
+        assert_eq!(
+            normalize_isbn13("978-0-13-110362-8"),
+            Err(FixError::CheckDigitIncorrect {
+                expected: 7,
+                found: 8
+            })
+        );
+        assert_eq!(normalize_isbn13("978-0-13-110362"), Err(FixError::TooShort));
+        assert_eq!(
+            normalize_isbn13("978-0-13-110362-70"),
+            Err(FixError::TooLong { trimmed_len: 14 })
+        );
+        assert_eq!(
+            normalize_isbn13("978 0 13 110362 7"),
+            Err(FixError::NonNumericString)
+        );
+        assert_eq!(normalize_isbn13("978-❤"), Err(FixError::NonAsciiString));
+        assert_eq!(
+            normalize_isbn13("400-6381-33393-1"),
+            Err(FixError::NotRepresentable)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn check_isbn10_doesnt_crash(ref s in ".*") {
+            check_isbn10(s);
+        }
+
+        #[test]
+        fn normalize_isbn13_doesnt_crash(ref s in ".*") {
+            if let Ok(code) = normalize_isbn13(s) {
+                assert!(gtin13::check(&code));
+            }
+        }
+
+        #[test]
+        fn isbn10_to_isbn13_passes_gtin13_check(ref s in "[0-9]{9}[0-9X]") {
+            if let Ok(isbn13) = isbn10_to_isbn13(s) {
+                assert!(gtin13::check(&isbn13));
+            }
+        }
+    }
+}
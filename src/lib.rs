@@ -9,16 +9,72 @@
 //! conversion.
 
 #![forbid(unsafe_code)]
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
 
 #[cfg(test)]
 #[macro_use]
 extern crate proptest;
 
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+#[cfg(feature = "futures")]
+extern crate futures;
+#[cfg(feature = "cache")]
+extern crate lru;
+#[cfg(feature = "prost")]
+extern crate prost;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[macro_use]
+mod macros;
+
 // private modules for internal use
+mod batch;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "io")]
+mod clean;
+mod context;
+mod digital_link;
+mod gs1;
+mod gtin;
+mod normalize;
+#[cfg(feature = "prost")]
+mod proto;
+mod scanner;
+mod set;
 mod utils;
+mod validator;
 
 // public modules
 pub mod gtin12;
 pub mod gtin13;
 pub mod gtin14;
 pub mod gtin8;
+pub mod isbn;
+
+pub use batch::{validate_batch, BatchSummary};
+#[cfg(feature = "cache")]
+pub use cache::CachedChecker;
+#[cfg(feature = "io")]
+pub use clean::{clean_column, CleanStats};
+pub use context::InputError;
+pub use digital_link::{from_digital_link, to_digital_link};
+pub use gs1::{gs1_prefix_region, GS1_PREFIX_TABLE};
+pub use gtin::{fix_best_effort, leading_zeros, Gtin, GtinError, GtinKind};
+pub use gtin12::Gtin12;
+pub use gtin13::Gtin13;
+pub use gtin14::Gtin14;
+pub use gtin8::Gtin8;
+pub use normalize::{normalize_dedup, normalize_to_gtin14, reconcile, Reconciliation};
+#[cfg(feature = "prost")]
+pub use proto::GtinProto;
+pub use scanner::{IncrementalScanner, ScanState};
+pub use set::{GtinSet12, GtinSet13, GtinSet14, GtinSet8};
+pub use utils::{weighted_sum, zero_pad};
+pub use validator::{
+    validator_for, Gtin12Validator, Gtin13Validator, Gtin14Validator, Gtin8Validator, Validator,
+};
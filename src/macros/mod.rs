@@ -0,0 +1,136 @@
+// Each GTIN module defines its own `FixError` with the same set of
+// variants. This macro implements lossless `From` conversions into the
+// given error type (the invoking module's `FixError` by default) from
+// the `FixError` of the listed modules, mapping every variant to the
+// variant of the same name.
+macro_rules! impl_fix_error_from {
+    ($($module:ident),+) => {
+        impl_fix_error_from!(FixError: $($module),+);
+    };
+    ($target:ident: $($module:ident),+) => {
+        $(
+            impl From<::$module::FixError> for $target {
+                fn from(err: ::$module::FixError) -> $target {
+                    match err {
+                        ::$module::FixError::NonAsciiString => $target::NonAsciiString,
+                        ::$module::FixError::NonNumericString => $target::NonNumericString,
+                        ::$module::FixError::TooLong { trimmed_len } => {
+                            $target::TooLong { trimmed_len }
+                        }
+                        ::$module::FixError::TooShort => $target::TooShort,
+                        ::$module::FixError::CheckDigitIncorrect { expected, found } => {
+                            $target::CheckDigitIncorrect { expected, found }
+                        }
+                        ::$module::FixError::Ambiguous => $target::Ambiguous,
+                        ::$module::FixError::NotRepresentable => $target::NotRepresentable,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+// Defines a newtype wrapping the digit string of a validated GTIN of the
+// invoking module's width. The module must provide `check`, and the
+// newtype must be named after its `GtinKind` variant.
+macro_rules! gtin_newtype {
+    ($(#[$attr:meta])* pub struct $name:ident($len:expr);) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(String);
+
+        impl $name {
+            // Wrap a code that is already known to pass `check`, such as
+            // the output of `fix`, without checking it again.
+            pub(crate) fn from_valid(code: String) -> $name {
+                debug_assert!(check(&code));
+                $name(code)
+            }
+
+            /// Return the digit string of the code.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Return the value (0 to 9) of the digit at `index`, counting
+            /// from 0 at the left, or `None` if `index` is past the end of
+            /// the code.
+            pub fn digit_at(&self, index: usize) -> Option<u8> {
+                self.0.as_bytes().get(index).map(|b| b - b'0')
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl ::std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl<'a> ::std::convert::TryFrom<&'a [u8]> for $name {
+            type Error = ::gtin::GtinError;
+
+            /// Validate raw bytes, which must be exactly the digits of
+            /// the code, without building a `String` first.
+            fn try_from(bytes: &'a [u8]) -> Result<$name, ::gtin::GtinError> {
+                let kind = Some(::gtin::GtinKind::$name);
+                let code = match ::std::str::from_utf8(bytes) {
+                    Ok(code) if code.is_ascii() => code,
+                    _ => return Err(::gtin::GtinError::NonAsciiString { kind }),
+                };
+                if code.len() > $len {
+                    return Err(::gtin::GtinError::TooLong {
+                        kind,
+                        trimmed_len: code.len(),
+                    });
+                }
+                if code.len() < $len {
+                    return Err(::gtin::GtinError::TooShort { kind });
+                }
+                if !::utils::is_ascii_numeric(code) {
+                    return Err(::gtin::GtinError::NonNumericString { kind });
+                }
+                if !check(code) {
+                    let (expected, found) = ::utils::check_digit_pair(code.as_bytes());
+                    return Err(::gtin::GtinError::CheckDigitIncorrect {
+                        kind,
+                        expected,
+                        found,
+                    });
+                }
+
+                Ok($name(code.to_string()))
+            }
+        }
+
+        impl<'a> ::std::convert::TryFrom<&'a str> for $name {
+            type Error = ::gtin::GtinError;
+
+            fn try_from(code: &'a str) -> Result<$name, ::gtin::GtinError> {
+                ::std::convert::TryFrom::try_from(code.as_bytes())
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::gtin::GtinError;
+
+            /// Validate a code as `TryFrom<&str>` does.
+            fn from_str(code: &str) -> Result<$name, ::gtin::GtinError> {
+                ::std::convert::TryFrom::try_from(code)
+            }
+        }
+
+        /// Serialize the digit string of the code.
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+    };
+}
@@ -0,0 +1,252 @@
+//! Normalization of codes of mixed widths to a common GTIN-14 form.
+
+use std::collections::HashSet;
+
+use gtin::GtinError;
+use gtin14;
+
+/// The result of comparing two lists of codes with
+/// [`reconcile`](fn.reconcile.html).
+///
+/// All codes are normalized to GTIN-14 and each list is sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Codes found only in the first list.
+    pub only_in_a: Vec<String>,
+    /// Codes found only in the second list.
+    pub only_in_b: Vec<String>,
+    /// Codes found in both lists.
+    pub in_both: Vec<String>,
+    /// The index and error of every input in the first list that could
+    /// not be fixed.
+    pub errors_a: Vec<(usize, GtinError)>,
+    /// The index and error of every input in the second list that could
+    /// not be fixed.
+    pub errors_b: Vec<(usize, GtinError)>,
+}
+
+/// Fix a code of any width and normalize it to a GTIN-14.
+///
+/// Whitespace is stripped from the left and right sides and the code is
+/// zero-padded to 14 digits. Zero-padding does not change the check
+/// digit of a GTIN, so GTIN-8, GTIN-12 and GTIN-13 codes all normalize
+/// to a valid GTIN-14 that identifies the same product.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::normalize_to_gtin14;
+///
+/// assert_eq!(normalize_to_gtin14("036000291452").unwrap(), "00036000291452");
+/// assert_eq!(normalize_to_gtin14("14567810 ").unwrap(), "00000014567810");
+/// assert!(normalize_to_gtin14("036000291453").is_err());
+/// ```
+pub fn normalize_to_gtin14(code: &str) -> Result<String, GtinError> {
+    Ok(gtin14::fix(code)?)
+}
+
+/// Fix and normalize a list of codes of mixed widths to GTIN-14, then
+/// remove duplicates.
+///
+/// Returns the set of unique GTIN-14 codes, together with the index and
+/// error of every input that could not be fixed.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{normalize_dedup, GtinError, GtinKind};
+///
+/// let (codes, errors) = normalize_dedup(&["036000291452", "0036000291452", "abc"]);
+/// assert_eq!(codes.len(), 1);
+/// assert!(codes.contains("00036000291452"));
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0, 2);
+/// assert_eq!(errors[0].1, GtinError::NonNumericString { kind: Some(GtinKind::Gtin14) });
+/// ```
+pub fn normalize_dedup(codes: &[&str]) -> (HashSet<String>, Vec<(usize, GtinError)>) {
+    let mut normalized = HashSet::new();
+    let mut errors = Vec::new();
+
+    for (index, code) in codes.iter().enumerate() {
+        match normalize_to_gtin14(code) {
+            Ok(gtin) => {
+                normalized.insert(gtin);
+            }
+            Err(err) => errors.push((index, err)),
+        }
+    }
+
+    (normalized, errors)
+}
+
+/// Compare two lists of codes of mixed widths.
+///
+/// Both lists are fixed, normalized to GTIN-14 and deduplicated as by
+/// [`normalize_dedup`](fn.normalize_dedup.html), so the same product
+/// written with different widths or whitespace is matched. Inputs that
+/// could not be fixed are reported per list and otherwise ignored.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::reconcile;
+///
+/// let result = reconcile(&["036000291452", "14567810"], &["0036000291452", "abc"]);
+/// assert_eq!(result.in_both, vec!["00036000291452"]);
+/// assert_eq!(result.only_in_a, vec!["00000014567810"]);
+/// assert!(result.only_in_b.is_empty());
+/// assert_eq!(result.errors_b.len(), 1);
+/// ```
+pub fn reconcile(a: &[&str], b: &[&str]) -> Reconciliation {
+    let (a, errors_a) = normalize_dedup(a);
+    let (b, errors_b) = normalize_dedup(b);
+
+    let sorted = |codes: Vec<&String>| {
+        let mut codes: Vec<String> = codes.into_iter().cloned().collect();
+        codes.sort_unstable();
+        codes
+    };
+
+    Reconciliation {
+        only_in_a: sorted(a.difference(&b).collect()),
+        only_in_b: sorted(b.difference(&a).collect()),
+        in_both: sorted(a.intersection(&b).collect()),
+        errors_a,
+        errors_b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_dedup;
+    use super::normalize_to_gtin14;
+    use super::reconcile;
+    use super::Reconciliation;
+    use gtin::{GtinError, GtinKind};
+
+    #[test]
+    fn normalize_to_gtin14_each_width() {
+        assert_eq!(normalize_to_gtin14("14567810").unwrap(), "00000014567810");
+        assert_eq!(
+            normalize_to_gtin14("897854613315").unwrap(),
+            "00897854613315"
+        );
+        assert_eq!(
+            normalize_to_gtin14("1498279802125").unwrap(),
+            "01498279802125"
+        );
+        assert_eq!(
+            normalize_to_gtin14("14567815983469").unwrap(),
+            "14567815983469"
+        );
+    }
+
+    #[test]
+    fn normalize_to_gtin14_errors() {
+        assert_eq!(
+            normalize_to_gtin14("❤"),
+            Err(GtinError::NonAsciiString {
+                kind: Some(GtinKind::Gtin14),
+            })
+        );
+        assert_eq!(
+            normalize_to_gtin14("000000000000000"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin14),
+                trimmed_len: 15,
+            })
+        );
+        assert_eq!(
+            normalize_to_gtin14("14567811"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin14),
+                expected: 0,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn normalize_dedup_mixed_widths() {
+        let (codes, errors) = normalize_dedup(&[
+            "036000291452",
+            " 0036000291452",
+            "00036000291452",
+            "14567810",
+            "14567811",
+            "000000000000000",
+        ]);
+        assert_eq!(codes.len(), 2);
+        assert!(codes.contains("00036000291452"));
+        assert!(codes.contains("00000014567810"));
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    4,
+                    GtinError::CheckDigitIncorrect {
+                        kind: Some(GtinKind::Gtin14),
+                        expected: 0,
+                        found: 1,
+                    }
+                ),
+                (
+                    5,
+                    GtinError::TooLong {
+                        kind: Some(GtinKind::Gtin14),
+                        trimmed_len: 15,
+                    }
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_dedup_empty() {
+        let (codes, errors) = normalize_dedup(&[]);
+        assert!(codes.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn reconcile_mixed_widths() {
+        let result = reconcile(
+            &["036000291452", "14567810", " 1498279802125", "14567811"],
+            &[
+                "0036000291452",
+                "00000014567810",
+                "14567815983469",
+                "000000000000000",
+            ],
+        );
+        assert_eq!(result.in_both, vec!["00000014567810", "00036000291452"]);
+        assert_eq!(result.only_in_a, vec!["01498279802125"]);
+        assert_eq!(result.only_in_b, vec!["14567815983469"]);
+        assert_eq!(
+            result.errors_a,
+            vec![(
+                3,
+                GtinError::CheckDigitIncorrect {
+                    kind: Some(GtinKind::Gtin14),
+                    expected: 0,
+                    found: 1,
+                }
+            )]
+        );
+        assert_eq!(
+            result.errors_b,
+            vec![(
+                3,
+                GtinError::TooLong {
+                    kind: Some(GtinKind::Gtin14),
+                    trimmed_len: 15,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn reconcile_empty() {
+        assert_eq!(reconcile(&[], &[]), Reconciliation::default());
+        let result = reconcile(&["036000291452"], &[]);
+        assert_eq!(result.only_in_a, vec!["00036000291452"]);
+        assert!(result.in_both.is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Protocol Buffers representation of the GTIN newtypes, available with
+//! the `prost` feature.
+
+use std::convert::TryFrom;
+
+use gtin::GtinError;
+use gtin12::Gtin12;
+use gtin13::Gtin13;
+use gtin14::Gtin14;
+use gtin8::Gtin8;
+
+/// A Protocol Buffers message carrying a GTIN code.
+///
+/// The wire representation is the canonical digit string of the code in
+/// field 1, equivalent to this `.proto` definition:
+///
+/// ```text
+/// message GtinProto {
+///   string code = 1;
+/// }
+/// ```
+///
+/// Converting a GTIN newtype into a message is infallible. Converting a
+/// message back into a newtype validates the code, so values received
+/// over the wire can be trusted once converted.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{Gtin13, GtinProto};
+/// use std::convert::TryFrom;
+///
+/// let code = Gtin13::try_from("4006381333931").unwrap();
+/// let message = GtinProto::from(code.clone());
+/// assert_eq!(message.code, "4006381333931");
+/// assert_eq!(Gtin13::try_from(message), Ok(code));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GtinProto {
+    /// The digit string of the code.
+    #[prost(string, tag = "1")]
+    pub code: String,
+}
+
+macro_rules! impl_proto_conversions {
+    ($($name:ident),+) => {
+        $(
+            impl From<$name> for GtinProto {
+                fn from(gtin: $name) -> GtinProto {
+                    GtinProto {
+                        code: gtin.as_str().to_string(),
+                    }
+                }
+            }
+
+            impl TryFrom<GtinProto> for $name {
+                type Error = GtinError;
+
+                fn try_from(message: GtinProto) -> Result<$name, GtinError> {
+                    $name::try_from(message.code.as_str())
+                }
+            }
+        )+
+    };
+}
+
+impl_proto_conversions!(Gtin8, Gtin12, Gtin13, Gtin14);
+
+#[cfg(test)]
+mod tests {
+    use super::GtinProto;
+    use gtin::{GtinError, GtinKind};
+    use gtin12::Gtin12;
+    use gtin13::Gtin13;
+    use gtin14::Gtin14;
+    use gtin8::Gtin8;
+    use prost::Message;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn round_trip_through_wire_format() {
+        let code = Gtin14::try_from("14567815983469").unwrap();
+        let bytes = GtinProto::from(code.clone()).encode_to_vec();
+        let message = GtinProto::decode(&bytes[..]).unwrap();
+        assert_eq!(Gtin14::try_from(message), Ok(code));
+    }
+
+    #[test]
+    fn from_each_newtype() {
+        let gtin8 = Gtin8::try_from("14567810").unwrap();
+        let gtin12 = Gtin12::try_from("897854613315").unwrap();
+        assert_eq!(GtinProto::from(gtin8).code, "14567810");
+        assert_eq!(GtinProto::from(gtin12).code, "897854613315");
+    }
+
+    #[test]
+    fn try_from_validates() {
+        let message = GtinProto {
+            code: "4006381333932".to_string(),
+        };
+        assert_eq!(
+            Gtin13::try_from(message.clone()),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 1,
+                found: 2,
+            })
+        );
+        assert_eq!(
+            Gtin12::try_from(message),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin12),
+                trimmed_len: 13
+            })
+        );
+        assert_eq!(
+            Gtin8::try_from(GtinProto::default()),
+            Err(GtinError::TooShort {
+                kind: Some(GtinKind::Gtin8)
+            })
+        );
+    }
+}
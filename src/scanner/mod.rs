@@ -0,0 +1,204 @@
+//! Assembly of validated codes from the characters typed by a
+//! keyboard-wedge barcode scanner.
+
+use gtin::Gtin;
+
+/// The outcome of feeding one character to an
+/// [`IncrementalScanner`](struct.IncrementalScanner.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanState {
+    /// More characters are needed before a code is complete.
+    Incomplete,
+    /// A complete, valid code was assembled. The scanner is ready for the
+    /// next code.
+    Complete(Gtin),
+    /// The characters since the last complete code do not form a valid
+    /// code. They are discarded and the scanner is ready for the next
+    /// code.
+    Invalid,
+}
+
+/// A buffer that accepts the characters of a code one at a time, as a
+/// keyboard-wedge scanner types them, and reports when a valid code has
+/// been assembled.
+///
+/// Digits are buffered until the scanner ends the code with a carriage
+/// return, line feed or tab, at which point the buffer is validated with
+/// [`Gtin::parse`](enum.Gtin.html#method.parse) at whichever width its
+/// length matches. A code is not completed as soon as the buffer reaches
+/// 8, 12 or 13 digits, because the start of a longer code may happen to
+/// be valid at a shorter width; only at 14 digits, the widest GTIN, is
+/// the code validated without waiting for a terminator.
+///
+/// A terminator with nothing buffered, such as the second half of a
+/// `\r\n` pair, is ignored. Any other character makes the buffered code
+/// invalid.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{Gtin, IncrementalScanner, ScanState};
+///
+/// let mut scanner = IncrementalScanner::new();
+/// for c in "400638133393".chars() {
+///     assert_eq!(scanner.push(c), ScanState::Incomplete);
+/// }
+/// assert_eq!(scanner.push('1'), ScanState::Incomplete);
+/// assert_eq!(
+///     scanner.push('\n'),
+///     ScanState::Complete(Gtin::Gtin13("4006381333931".parse().unwrap()))
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalScanner {
+    buffer: String,
+}
+
+impl IncrementalScanner {
+    /// Create a scanner with an empty buffer.
+    pub fn new() -> IncrementalScanner {
+        IncrementalScanner::default()
+    }
+
+    /// Feed the next character typed by the scanner.
+    pub fn push(&mut self, c: char) -> ScanState {
+        match c {
+            '0'..='9' => {
+                self.buffer.push(c);
+                if self.buffer.len() < 14 {
+                    return ScanState::Incomplete;
+                }
+                self.finish()
+            }
+            '\r' | '\n' | '\t' if self.buffer.is_empty() => ScanState::Incomplete,
+            '\r' | '\n' | '\t' => self.finish(),
+            _ => {
+                self.buffer.clear();
+                ScanState::Invalid
+            }
+        }
+    }
+
+    /// Return the digits buffered since the last complete or invalid code.
+    pub fn pending(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Discard any buffered digits.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    // Validate the buffered digits as a complete code and clear the
+    // buffer.
+    fn finish(&mut self) -> ScanState {
+        let state = match Gtin::parse(&self.buffer) {
+            Ok(gtin) => ScanState::Complete(gtin),
+            Err(_) => ScanState::Invalid,
+        };
+        self.buffer.clear();
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IncrementalScanner;
+    use super::ScanState;
+    use gtin::Gtin;
+
+    // Feed every character, returning the states that were not
+    // `Incomplete`.
+    fn feed(scanner: &mut IncrementalScanner, input: &str) -> Vec<ScanState> {
+        input
+            .chars()
+            .map(|c| scanner.push(c))
+            .filter(|state| *state != ScanState::Incomplete)
+            .collect()
+    }
+
+    #[test]
+    fn one_character_at_a_time() {
+        let mut scanner = IncrementalScanner::new();
+        for (i, c) in "036000291452".chars().enumerate() {
+            assert_eq!(scanner.push(c), ScanState::Incomplete);
+            assert_eq!(scanner.pending().len(), i + 1);
+        }
+        assert_eq!(
+            scanner.push('\r'),
+            ScanState::Complete(Gtin::Gtin12("036000291452".parse().unwrap()))
+        );
+        assert_eq!(scanner.push('\n'), ScanState::Incomplete);
+        assert_eq!(scanner.pending(), "");
+    }
+
+    #[test]
+    fn each_width() {
+        let mut scanner = IncrementalScanner::new();
+        assert_eq!(
+            feed(
+                &mut scanner,
+                "14567810\r\n897854613315\r\n1498279802125\r\n14567815983469"
+            ),
+            vec![
+                ScanState::Complete(Gtin::Gtin8("14567810".parse().unwrap())),
+                ScanState::Complete(Gtin::Gtin12("897854613315".parse().unwrap())),
+                ScanState::Complete(Gtin::Gtin13("1498279802125".parse().unwrap())),
+                ScanState::Complete(Gtin::Gtin14("14567815983469".parse().unwrap())),
+            ]
+        );
+    }
+
+    #[test]
+    fn valid_prefix_does_not_complete() {
+        // The first 8 digits form a valid GTIN-8
+        let mut scanner = IncrementalScanner::new();
+        assert_eq!(feed(&mut scanner, "14567810"), vec![]);
+        assert_eq!(
+            feed(&mut scanner, "59834\n"),
+            vec![ScanState::Invalid] // 13 digits, bad check digit
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let mut scanner = IncrementalScanner::new();
+        assert_eq!(
+            feed(&mut scanner, "4006381333932\n"),
+            vec![ScanState::Invalid]
+        );
+        assert_eq!(feed(&mut scanner, "1234\n"), vec![ScanState::Invalid]);
+        assert_eq!(feed(&mut scanner, "40063a"), vec![ScanState::Invalid]);
+        assert_eq!(scanner.pending(), "");
+        assert_eq!(
+            feed(&mut scanner, "14567810\n"),
+            vec![ScanState::Complete(Gtin::Gtin8(
+                "14567810".parse().unwrap()
+            ))]
+        );
+    }
+
+    #[test]
+    fn reset_discards_digits() {
+        let mut scanner = IncrementalScanner::new();
+        feed(&mut scanner, "4006");
+        scanner.reset();
+        assert_eq!(scanner.pending(), "");
+        assert_eq!(
+            feed(&mut scanner, "14567810\t"),
+            vec![ScanState::Complete(Gtin::Gtin8(
+                "14567810".parse().unwrap()
+            ))]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn push_doesnt_crash(ref s in ".*") {
+            let mut scanner = IncrementalScanner::new();
+            for c in s.chars() {
+                scanner.push(c);
+                assert!(scanner.pending().len() < 14);
+            }
+        }
+    }
+}
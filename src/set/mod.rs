@@ -0,0 +1,187 @@
+//! Sets holding only valid, deduplicated GTIN codes of a single width.
+
+use std::collections::hash_set;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+
+use gtin12::Gtin12;
+use gtin13::Gtin13;
+use gtin14::Gtin14;
+use gtin8::Gtin8;
+
+macro_rules! gtin_set {
+    ($(#[$attr:meta])* pub struct $name:ident($gtin:ident);) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct $name(HashSet<$gtin>);
+
+        impl $name {
+            /// Create an empty set.
+            pub fn new() -> $name {
+                $name(HashSet::new())
+            }
+
+            /// Validate a code and add it to the set.
+            ///
+            /// The code is not fixed, so it must be exactly the digits of
+            /// a valid code. Returns true if the code was valid and not
+            /// yet in the set.
+            pub fn insert(&mut self, code: &str) -> bool {
+                match $gtin::try_from(code) {
+                    Ok(gtin) => self.0.insert(gtin),
+                    Err(_) => false,
+                }
+            }
+
+            /// Return true if the set contains the code.
+            pub fn contains(&self, code: &str) -> bool {
+                self.0.contains(code)
+            }
+
+            /// Return the number of codes in the set.
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Return true if the set contains no codes.
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Iterate over the codes in the set, in arbitrary order.
+            pub fn iter(&self) -> hash_set::Iter<'_, $gtin> {
+                self.0.iter()
+            }
+        }
+
+        impl FromIterator<$gtin> for $name {
+            fn from_iter<I: IntoIterator<Item = $gtin>>(iter: I) -> $name {
+                $name(iter.into_iter().collect())
+            }
+        }
+
+        impl Extend<$gtin> for $name {
+            fn extend<I: IntoIterator<Item = $gtin>>(&mut self, iter: I) {
+                self.0.extend(iter)
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $name {
+            type Item = &'a $gtin;
+            type IntoIter = hash_set::Iter<'a, $gtin>;
+
+            fn into_iter(self) -> hash_set::Iter<'a, $gtin> {
+                self.0.iter()
+            }
+        }
+
+        impl IntoIterator for $name {
+            type Item = $gtin;
+            type IntoIter = hash_set::IntoIter<$gtin>;
+
+            fn into_iter(self) -> hash_set::IntoIter<$gtin> {
+                self.0.into_iter()
+            }
+        }
+    };
+}
+
+gtin_set! {
+    /// A set of valid, deduplicated GTIN-8 codes.
+    ///
+    /// See [`GtinSet13`](struct.GtinSet13.html) for an example.
+    pub struct GtinSet8(Gtin8);
+}
+
+gtin_set! {
+    /// A set of valid, deduplicated GTIN-12 (UPC-A) codes.
+    ///
+    /// See [`GtinSet13`](struct.GtinSet13.html) for an example.
+    pub struct GtinSet12(Gtin12);
+}
+
+gtin_set! {
+    /// A set of valid, deduplicated GTIN-13 (EAN-13) codes.
+    ///
+    /// # Examples
+    /// ```
+    /// use gtin_validate::{Gtin13, GtinSet13};
+    /// use std::convert::TryFrom;
+    ///
+    /// let codes = ["4006381333931", "4006381333931", "4006381333932"];
+    /// let mut set: GtinSet13 = codes
+    ///     .iter()
+    ///     .filter_map(|code| Gtin13::try_from(*code).ok())
+    ///     .collect();
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// assert_eq!(set.insert("1498279802125"), true);  // New and valid
+    /// assert_eq!(set.insert("1498279802125"), false); // Duplicate
+    /// assert_eq!(set.insert("1498279802124"), false); // Bad check digit
+    /// assert!(set.contains("1498279802125"));
+    /// ```
+    pub struct GtinSet13(Gtin13);
+}
+
+gtin_set! {
+    /// A set of valid, deduplicated GTIN-14 codes.
+    ///
+    /// See [`GtinSet13`](struct.GtinSet13.html) for an example.
+    pub struct GtinSet14(Gtin14);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GtinSet12;
+    use super::GtinSet13;
+    use super::GtinSet14;
+    use super::GtinSet8;
+    use gtin13::Gtin13;
+    use gtin8::Gtin8;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn insert_valid_and_new() {
+        let mut set = GtinSet12::new();
+        assert!(set.is_empty());
+        assert_eq!(set.insert("036000291452"), true);
+        assert_eq!(set.insert("036000291452"), false);
+        assert_eq!(set.insert("036000291453"), false);
+        assert_eq!(set.insert(" 036000291452"), false); // not fixed
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn contains() {
+        let mut set = GtinSet14::new();
+        set.insert("14567815983469");
+        assert!(set.contains("14567815983469"));
+        assert!(!set.contains("04006381333931"));
+        assert!(!set.contains("❤"));
+    }
+
+    #[test]
+    fn collect_and_iterate() {
+        let set: GtinSet8 = ["14567810", "49137712", "14567810"]
+            .iter()
+            .filter_map(|code| Gtin8::try_from(*code).ok())
+            .collect();
+        let mut codes: Vec<&str> = set.iter().map(|gtin| gtin.as_str()).collect();
+        codes.sort();
+        assert_eq!(codes, vec!["14567810", "49137712"]);
+        assert_eq!(set.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn extend() {
+        let mut set = GtinSet13::new();
+        set.insert("4006381333931");
+        set.extend(vec![
+            Gtin13::try_from("4006381333931").unwrap(),
+            Gtin13::try_from("1498279802125").unwrap(),
+        ]);
+        assert_eq!(set.len(), 2);
+        assert_eq!((&set).into_iter().count(), 2);
+    }
+}
@@ -1,63 +1,177 @@
+use std::borrow::Cow;
+
 /// Compute the check digit for a GTIN code as described on the
 /// [GS1 website](http://www.gs1.org/how-calculate-check-digit-manually)
 /// This function assumes that the passed in bytes are already
 /// guaranteed to be ASCII digits (eg, by calling is_ascii_numeric).
+///
+/// The last byte is the check digit position and is not included.
+#[inline]
 pub fn compute_check_digit(bytes: &[u8]) -> u8 {
-    let mut even: u16 = 0;
-    let mut odd: u16 = 0;
-    let mut check: u8;
-    let mut curr: u8;
-
-    // Read GTIN in reverse because the even/odd columns are defined
-    // right-to-left, with the last non-check-digit column being odd.
-    for i in 2..bytes.len() + 1 {
-        curr = bytes[bytes.len() - i] - 48;
-        if i % 2 == 0 {
-            odd += u16::from(curr);
-        } else {
-            even += u16::from(curr);
-        }
-    }
+    compute_mod10(bytes)
+}
 
-    check = ((3 * odd + even) % 10) as u8;
-    if check > 0 {
-        check = 10 - check;
-    }
+/// Return the check digit computed for a code and the check digit the
+/// code actually ends with, in that order. Like `compute_check_digit`,
+/// this assumes the bytes are ASCII digits, and there must be at least
+/// one.
+#[inline]
+pub fn check_digit_pair(bytes: &[u8]) -> (u8, u8) {
+    (compute_check_digit(bytes), bytes[bytes.len() - 1] - b'0')
+}
+
+/// The GS1 mod-10 algorithm: the digits are weighted 3, 1, 3, ... from
+/// the right, starting with the last non-check-digit column, and the
+/// check digit brings the weighted sum up to a multiple of 10.
+#[inline]
+fn compute_mod10(bytes: &[u8]) -> u8 {
+    let remainder = weighted_sum(bytes) % 10;
+
+    ((10 - remainder) % 10) as u8
+}
+
+/// Compute the weighted sum of the GS1 mod-10 algorithm, before it is
+/// reduced modulo 10.
+///
+/// The last byte is the check digit position and is not included, so a
+/// complete code may be passed. The remaining digits are weighted 3, 1,
+/// 3, ... from the right, so the sum is three times the digits in odd
+/// positions plus the digits in even positions, counting from the right
+/// of the body. The check digit is the amount that brings this sum up to
+/// a multiple of 10.
+///
+/// The bytes must be ASCII digits, as checked by the `check` functions;
+/// the sum of any other bytes is meaningless.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::weighted_sum;
+///
+/// // 3 * (0 + 6 + 0 + 2 + 1 + 5) + (3 + 0 + 0 + 9 + 4) = 58, so the check digit is 2
+/// assert_eq!(weighted_sum(b"036000291452"), 58);
+/// assert_eq!(weighted_sum(b"4006381333931"), 89);
+/// ```
+#[inline]
+pub fn weighted_sum(bytes: &[u8]) -> u32 {
+    let body = match bytes.split_last() {
+        Some((_, body)) => body,
+        None => return 0,
+    };
 
-    check
+    weighted_total(body, &[3, 1])
+}
+
+/// Compute the weighted sum of a string of ASCII digits, modulo
+/// `modulus`.
+///
+/// The weights are applied from the right: `weights[0]` to the last
+/// byte, `weights[1]` to the one before it, and so on, repeating the
+/// weights if there are more digits than weights. Like
+/// `compute_check_digit`, this assumes the bytes are ASCII digits. The
+/// sum is accumulated in a `u32`, which is ample for codes of any
+/// realistic length. `weights` must not be empty and `modulus` must not
+/// be zero.
+#[inline]
+pub fn compute_weighted(bytes: &[u8], weights: &[u8], modulus: u16) -> u16 {
+    (weighted_total(bytes, weights) % u32::from(modulus)) as u16
+}
+
+// Sum the digits weighted from the right, repeating the weights, without
+// reducing the result. Shared by `weighted_sum` and `compute_weighted`.
+#[inline]
+fn weighted_total(bytes: &[u8], weights: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .rev()
+        .zip(weights.iter().cycle())
+        .map(|(&b, &weight)| u32::from(b.wrapping_sub(b'0')) * u32::from(weight))
+        .sum()
 }
 
-/// Add zeros to the left side of a string so that it matches the
-/// desired length.
+/// Add zeros to the left side of a code so that it is `width`
+/// characters long.
 ///
-/// If the string is longer than the desired length, it is returned,
-/// without modification.
-pub fn zero_pad(upc: String, size: usize) -> String {
-    if upc.len() >= size {
-        return upc;
-    }
-    let mut padded = String::with_capacity(size);
-    for _ in 0..size - upc.len() {
+/// A code that is already at least `width` bytes long is borrowed
+/// unchanged, so padding codes that are usually complete costs no
+/// allocation. Padding with zeros does not change the check digit of a
+/// GTIN, which is how the crate converts between widths.
+///
+/// No validation is performed: the code is padded whatever it contains.
+/// Use the `check` or `fix` function of the target width to validate
+/// the result.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::zero_pad;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(zero_pad("36000291452", 12), "036000291452");
+/// assert!(matches!(zero_pad("036000291452", 12), Cow::Borrowed(_)));
+/// assert_eq!(zero_pad("abc", 5), "00abc"); // Not validated
+/// ```
+pub fn zero_pad(code: &str, width: usize) -> Cow<'_, str> {
+    if code.len() >= width {
+        return Cow::Borrowed(code);
+    }
+    let mut padded = String::with_capacity(width);
+    for _ in 0..width - code.len() {
         padded.push('0');
     }
-    padded.push_str(&upc);
+    padded.push_str(code);
 
-    padded
+    Cow::Owned(padded)
 }
 
+/// Check that every byte of the string is an ASCII digit.
+///
+/// The scan works on raw bytes rather than decoded characters and stops
+/// at the first non-digit, so non-numeric input is rejected cheaply.
 #[inline]
 pub fn is_ascii_numeric(s: &str) -> bool {
-    s.chars().all(|c| c.is_ascii_digit())
+    s.bytes().all(|b| b.is_ascii_digit())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::check_digit_pair;
     use super::compute_check_digit;
+    use super::compute_mod10;
+    use super::compute_weighted;
     use super::is_ascii_numeric;
+    use super::weighted_sum;
     use super::zero_pad;
+    use gtin12;
+    use gtin13;
+    use gtin14;
+    use gtin8;
+    use std::borrow::Cow;
 
     use proptest::prelude::*;
 
+    /// A deliberately plain reference for the `check` functions: weight
+    /// the digits 3, 1, 3, ... from the right, excluding the check digit,
+    /// and require the whole code to sum to a multiple of 10. Any faster
+    /// implementation of `check` must agree with it.
+    fn reference_check(code: &str, width: usize) -> bool {
+        if code.len() != width || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        let sum: u32 = code
+            .bytes()
+            .rev()
+            .enumerate()
+            .map(|(i, b)| {
+                let digit = u32::from(b - b'0');
+                if i % 2 == 1 {
+                    3 * digit
+                } else {
+                    digit
+                }
+            })
+            .sum();
+        sum.is_multiple_of(10)
+    }
+
     #[test]
     fn compute_check_digit_static_data() {
         assert_eq!(compute_check_digit("000000000000".as_bytes()), 0);
@@ -73,16 +187,87 @@ mod tests {
         assert_eq!(compute_check_digit("92498743135447".as_bytes()), 7);
     }
 
+    #[test]
+    fn compute_check_digit_empty() {
+        assert_eq!(compute_check_digit(b""), 0);
+        assert_eq!(compute_check_digit(b"7"), 0);
+    }
+
+    #[test]
+    fn check_digit_pair_static_data() {
+        assert_eq!(check_digit_pair(b"4006381333931"), (1, 1));
+        assert_eq!(check_digit_pair(b"4006381333932"), (1, 2));
+        assert_eq!(check_digit_pair(b"14567811"), (0, 1));
+        assert_eq!(check_digit_pair(b"7"), (0, 7));
+    }
+
+    #[test]
+    fn compute_weighted_static_data() {
+        assert_eq!(compute_weighted(b"", &[3, 1], 10), 0);
+        assert_eq!(compute_weighted(b"12", &[3, 1], 10), 7);
+        assert_eq!(compute_weighted(b"03600029145", &[3, 1], 10), 8);
+        // ISBN-10 body weighted 2..10 from the right: 0131103628
+        assert_eq!(
+            compute_weighted(b"013110362", &[2, 3, 4, 5, 6, 7, 8, 9, 10], 11),
+            3
+        );
+        // SSCC-18 uses the same mod-10 weights.
+        assert_eq!(compute_weighted(b"00000000000000001", &[3, 1], 10), 3);
+    }
+
+    #[test]
+    fn weighted_sum_static_data() {
+        assert_eq!(weighted_sum(b"000000000000"), 0);
+        assert_eq!(weighted_sum(b"123456789012"), 98);
+        assert_eq!(weighted_sum(b"123456789081"), 119);
+        assert_eq!(weighted_sum(b"036000291452"), 58);
+        assert_eq!(weighted_sum(b"999999999993"), 207);
+        assert_eq!(weighted_sum(b"0000000000000"), 0);
+        assert_eq!(weighted_sum(b"1234123412344"), 66);
+        assert_eq!(weighted_sum(b"9249874313545"), 115);
+        assert_eq!(weighted_sum(b"00000000000000"), 0);
+        assert_eq!(weighted_sum(b"01010101010104"), 6);
+        assert_eq!(weighted_sum(b"92498743135447"), 133);
+    }
+
+    #[test]
+    fn weighted_sum_empty() {
+        assert_eq!(weighted_sum(b""), 0);
+        assert_eq!(weighted_sum(b"7"), 0);
+    }
+
     #[test]
     fn zero_pad_static_data() {
-        assert_eq!(zero_pad("hello".to_string(), 6), "0hello".to_string());
-        assert_eq!(zero_pad("".to_string(), 0), "".to_string());
+        assert_eq!(zero_pad("hello", 6), "0hello");
+        assert_eq!(zero_pad("", 0), "");
+        assert_eq!(zero_pad("", 3), "000");
     }
 
     #[test]
     fn zero_pad_string_longer_than_desired_length() {
-        assert_eq!(zero_pad("hello".to_string(), 3), "hello".to_string());
-        assert_eq!(zero_pad("hello".to_string(), 0), "hello".to_string());
+        assert_eq!(zero_pad("hello", 3), "hello");
+        assert_eq!(zero_pad("hello", 0), "hello");
+    }
+
+    #[test]
+    fn zero_pad_borrows_when_long_enough() {
+        assert!(matches!(
+            zero_pad("036000291452", 12),
+            Cow::Borrowed("036000291452")
+        ));
+        assert!(matches!(zero_pad("4006381333931", 12), Cow::Borrowed(_)));
+        assert!(matches!(zero_pad("", 0), Cow::Borrowed("")));
+    }
+
+    #[test]
+    fn zero_pad_owns_when_padded() {
+        match zero_pad("36000291452", 12) {
+            Cow::Owned(padded) => {
+                assert_eq!(padded, "036000291452");
+                assert_eq!(padded.capacity(), 12);
+            }
+            Cow::Borrowed(_) => panic!("expected an owned string"),
+        }
     }
 
     #[test]
@@ -106,5 +291,38 @@ mod tests {
         fn compute_check_digit_doesnt_crash(ref code in "[0-9]*") {
             assert!(compute_check_digit(code.as_bytes()) < 10);
         }
+
+        #[test]
+        fn compute_mod10_completes_multiple_of_ten(ref code in "[0-9]{1,20}") {
+            let mut bytes = code.clone().into_bytes();
+            bytes.push(b'0');
+            let check = compute_mod10(&bytes);
+            *bytes.last_mut().unwrap() = b'0' + check;
+            assert_eq!(compute_weighted(&bytes, &[1, 3], 10), 0);
+        }
+
+        #[test]
+        fn weighted_sum_completes_check_digit(ref code in "[0-9]{1,20}") {
+            let sum = weighted_sum(code.as_bytes());
+            let check = compute_check_digit(code.as_bytes());
+            assert!((sum + u32::from(check)).is_multiple_of(10));
+        }
+
+        #[test]
+        fn check_matches_reference(ref code in "[0-9]{7,15}") {
+            assert_eq!(gtin8::check(code), reference_check(code, 8));
+            assert_eq!(gtin12::check(code), reference_check(code, 12));
+            assert_eq!(gtin13::check(code), reference_check(code, 13));
+            assert_eq!(gtin14::check(code), reference_check(code, 14));
+        }
+
+        // Characters just outside the digit range catch range-check
+        // mistakes that all-digit input cannot.
+        #[test]
+        fn check_matches_reference_near_digits(ref code in "[/0-9:]{12,14}") {
+            assert_eq!(gtin12::check(code), reference_check(code, 12));
+            assert_eq!(gtin13::check(code), reference_check(code, 13));
+            assert_eq!(gtin14::check(code), reference_check(code, 14));
+        }
     }
 }
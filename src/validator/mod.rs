@@ -0,0 +1,168 @@
+//! A trait over the per-width validation functions, for choosing the
+//! width at run time.
+
+use gtin::{GtinError, GtinKind};
+use gtin12;
+use gtin13;
+use gtin14;
+use gtin8;
+
+/// The `check` and `fix` functions of one GTIN width, as a trait that can
+/// be used as a trait object.
+///
+/// Each width has a zero-sized implementor that forwards to the free
+/// functions of its module, such as [`Gtin13Validator`] to
+/// [`gtin13::check`](gtin13/fn.check.html) and
+/// [`gtin13::fix`](gtin13/fn.fix.html). Use
+/// [`validator_for`](fn.validator_for.html) to pick one by kind, for
+/// example from a configuration file.
+///
+/// [`Gtin13Validator`]: struct.Gtin13Validator.html
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{validator_for, GtinKind, Validator};
+///
+/// let validators: Vec<&dyn Validator> = vec![
+///     validator_for(GtinKind::Gtin12),
+///     validator_for(GtinKind::Gtin13),
+/// ];
+/// let valid: Vec<GtinKind> = validators
+///     .iter()
+///     .filter(|v| v.check("036000291452"))
+///     .map(|v| v.kind())
+///     .collect();
+/// assert_eq!(valid, vec![GtinKind::Gtin12]);
+/// ```
+pub trait Validator {
+    /// Check that a code of this width is valid, as the module's `check`
+    /// function does.
+    fn check(&self, code: &str) -> bool;
+
+    /// Fix a code of this width, as the module's `fix` function does.
+    fn fix(&self, code: &str) -> Result<String, GtinError>;
+
+    /// Return the width this validator handles.
+    fn kind(&self) -> GtinKind;
+}
+
+macro_rules! validator {
+    ($(#[$attr:meta])* $name:ident, $module:ident, $kind:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl Validator for $name {
+            fn check(&self, code: &str) -> bool {
+                $module::check(code)
+            }
+
+            fn fix(&self, code: &str) -> Result<String, GtinError> {
+                Ok($module::fix(code)?)
+            }
+
+            fn kind(&self) -> GtinKind {
+                GtinKind::$kind
+            }
+        }
+    };
+}
+
+validator!(
+    /// The [`Validator`](trait.Validator.html) for GTIN-8 codes.
+    Gtin8Validator,
+    gtin8,
+    Gtin8
+);
+validator!(
+    /// The [`Validator`](trait.Validator.html) for GTIN-12 codes.
+    Gtin12Validator,
+    gtin12,
+    Gtin12
+);
+validator!(
+    /// The [`Validator`](trait.Validator.html) for GTIN-13 codes.
+    Gtin13Validator,
+    gtin13,
+    Gtin13
+);
+validator!(
+    /// The [`Validator`](trait.Validator.html) for GTIN-14 codes.
+    Gtin14Validator,
+    gtin14,
+    Gtin14
+);
+
+/// Return the [`Validator`](trait.Validator.html) for a GTIN width.
+///
+/// # Examples
+/// ```
+/// use gtin_validate::{validator_for, GtinKind};
+///
+/// let validator = validator_for(GtinKind::Gtin13);
+/// assert_eq!(validator.check("4006381333931"), true);
+/// assert_eq!(validator.fix("495205944325").unwrap(), "0495205944325");
+/// ```
+pub fn validator_for(kind: GtinKind) -> &'static dyn Validator {
+    match kind {
+        GtinKind::Gtin8 => &Gtin8Validator,
+        GtinKind::Gtin12 => &Gtin12Validator,
+        GtinKind::Gtin13 => &Gtin13Validator,
+        GtinKind::Gtin14 => &Gtin14Validator,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validator_for;
+    use super::Gtin13Validator;
+    use super::Validator;
+    use gtin::{GtinError, GtinKind};
+
+    #[test]
+    fn validator_for_each_kind() {
+        let cases = [
+            (GtinKind::Gtin8, "14567810", "4567813"),
+            (GtinKind::Gtin12, "036000291452", "36000291452"),
+            (GtinKind::Gtin13, "4006381333931", "495205944325"),
+            (GtinKind::Gtin14, "14567815983469", "4006381333931"),
+        ];
+        for &(kind, valid, paddable) in &cases {
+            let validator = validator_for(kind);
+            assert_eq!(validator.kind(), kind);
+            assert_eq!(validator.check(valid), true);
+            assert_eq!(validator.check(paddable), false);
+            assert_eq!(validator.fix(valid).unwrap(), valid);
+            assert_eq!(validator.fix(paddable).unwrap().len(), kind.length());
+        }
+    }
+
+    #[test]
+    fn validator_fix_errors_carry_kind() {
+        assert_eq!(
+            validator_for(GtinKind::Gtin13).fix("4006381333932"),
+            Err(GtinError::CheckDigitIncorrect {
+                kind: Some(GtinKind::Gtin13),
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            validator_for(GtinKind::Gtin8).fix("4006381333931"),
+            Err(GtinError::TooLong {
+                kind: Some(GtinKind::Gtin8),
+                trimmed_len: 13
+            })
+        );
+    }
+
+    #[test]
+    fn boxed_validator() {
+        let validators: Vec<Box<dyn Validator>> =
+            vec![Box::new(Gtin13Validator), Box::new(Gtin13Validator)];
+        for validator in &validators {
+            assert_eq!(validator.check("4006381333931"), true);
+            assert_eq!(validator.kind(), GtinKind::Gtin13);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Invariants of the conversions between GTIN widths: every converted
+//! code must pass the `check` of its target width, and conversions that
+//! are inverses of each other must round-trip.
+
+extern crate gtin_validate;
+extern crate proptest;
+
+use gtin_validate::{from_digital_link, normalize_to_gtin14, to_digital_link, Gtin};
+use gtin_validate::{gtin12, gtin13, gtin14, gtin8, isbn};
+
+use proptest::prelude::*;
+
+// Complete a body of up to 13 digits with its check digit. Zero-padding
+// does not change the check digit, so the body is computed as a GTIN-14
+// and the padding removed again.
+fn with_check_digit(body: &str) -> String {
+    let padded = format!("{:0>13}0", body);
+    let code = gtin14::recompute_check_digit(&padded).unwrap();
+    code[13 - body.len()..].to_string()
+}
+
+// Generate valid codes of the given width.
+fn valid_code(width: usize) -> BoxedStrategy<String> {
+    proptest::string::string_regex(&format!("[0-9]{{{}}}", width - 1))
+        .unwrap()
+        .prop_map(|body| with_check_digit(&body))
+        .boxed()
+}
+
+// Generate valid codes of every width.
+fn any_valid_code() -> BoxedStrategy<String> {
+    prop_oneof![
+        valid_code(8),
+        valid_code(12),
+        valid_code(13),
+        valid_code(14)
+    ]
+    .boxed()
+}
+
+#[test]
+fn with_check_digit_is_valid() {
+    assert_eq!(with_check_digit("1456781"), "14567810");
+    assert_eq!(with_check_digit("03600029145"), "036000291452");
+    assert_eq!(with_check_digit("400638133393"), "4006381333931");
+    assert_eq!(with_check_digit("1456781598346"), "14567815983469");
+}
+
+proptest! {
+    #[test]
+    fn generated_codes_are_valid(ref code in any_valid_code()) {
+        assert!(Gtin::parse(code).is_ok());
+    }
+
+    #[test]
+    fn gtin8_to_gtin13_is_valid(ref code in valid_code(8)) {
+        let gtin13 = gtin8::to_gtin13(code).unwrap();
+        assert!(gtin13::check(&gtin13));
+        assert_eq!(&gtin13[5..], code.as_str());
+    }
+
+    #[test]
+    fn gtin8_to_gtin14_is_valid(ref code in valid_code(8)) {
+        let gtin14 = gtin8::to_gtin14(code).unwrap();
+        assert!(gtin14::check(&gtin14));
+        assert_eq!(&gtin14[6..], code.as_str());
+        assert_eq!(gtin14::shortest_kind(&gtin14).map(|kind| kind.length()), Some(8));
+    }
+
+    #[test]
+    fn gtin8_conversions_agree(ref code in valid_code(8)) {
+        let via_gtin13 = gtin14::fix(gtin8::to_gtin13(code).unwrap()).unwrap();
+        assert_eq!(via_gtin13, gtin8::to_gtin14(code).unwrap());
+    }
+
+    #[test]
+    fn upca_round_trips_through_gtin13(ref code in valid_code(12)) {
+        let gtin13 = gtin13::parse_accepting_upca(code).unwrap();
+        assert!(gtin13::check(&gtin13));
+        let upca = gtin13::to_gtin12(&gtin13).unwrap();
+        assert!(gtin12::check(&upca));
+        assert_eq!(&upca, code);
+    }
+
+    #[test]
+    fn gtin13_round_trips_through_upca(ref code in valid_code(13)) {
+        match gtin13::to_gtin12(code) {
+            Ok(upca) => {
+                assert!(code.starts_with('0'));
+                assert!(gtin12::check(&upca));
+                assert_eq!(&gtin13::parse_accepting_upca(&upca).unwrap(), code);
+            }
+            Err(_) => assert!(!code.starts_with('0')),
+        }
+    }
+
+    #[test]
+    fn to_gtin14_is_valid(ref code in any_valid_code()) {
+        let gtin = Gtin::parse(code).unwrap();
+        let gtin14 = gtin.to_gtin14();
+        assert!(gtin14::check(&gtin14));
+        assert_eq!(normalize_to_gtin14(code), Ok(gtin14.clone()));
+        assert!(gtin.same_product(&Gtin::parse(&gtin14).unwrap()));
+    }
+
+    #[test]
+    fn gtin14_round_trips_through_shortest_kind(ref code in any_valid_code()) {
+        let gtin14 = normalize_to_gtin14(code).unwrap();
+        let width = gtin14::shortest_kind(&gtin14).unwrap().length();
+        let shortest = &gtin14[14 - width..];
+        assert!(Gtin::parse(shortest).is_ok());
+        assert!(width <= code.len());
+        assert_eq!(normalize_to_gtin14(shortest), Ok(gtin14.clone()));
+    }
+
+    #[test]
+    fn recompute_check_digit_is_valid(ref code in "[0-9]{14}") {
+        let fixed = gtin14::recompute_check_digit(code).unwrap();
+        assert!(gtin14::check(&fixed));
+        assert_eq!(gtin14::recompute_check_digit(&fixed).unwrap(), fixed);
+    }
+
+    #[test]
+    fn isbn10_to_isbn13_is_valid(ref body in "[0-9]{9}") {
+        let isbn10_check = body
+            .bytes()
+            .enumerate()
+            .map(|(i, b)| (10 - i as u32) * u32::from(b - b'0'))
+            .sum::<u32>();
+        let check = match (11 - isbn10_check % 11) % 11 {
+            10 => 'X',
+            digit => (b'0' + digit as u8) as char,
+        };
+        let isbn10 = format!("{}{}", body, check);
+        assert!(isbn::check_isbn10(&isbn10));
+        let isbn13 = isbn::isbn10_to_isbn13(&isbn10).unwrap();
+        assert!(gtin13::check(&isbn13));
+        assert_eq!(&isbn13[..3], "978");
+        assert_eq!(&isbn13[3..12], body.as_str());
+    }
+
+    #[test]
+    fn digital_link_round_trips(ref code in any_valid_code()) {
+        let url = to_digital_link(code, "").unwrap();
+        let gtin = from_digital_link(&url).unwrap();
+        assert!(gtin14::check(gtin.as_str()));
+        assert!(gtin.same_product(&Gtin::parse(code).unwrap()));
+    }
+}